@@ -0,0 +1,464 @@
+//! Typed [WAI-ARIA 1.2](https://www.w3.org/TR/wai-aria-1.2/) roles, states, and properties.
+//!
+//! ```
+//! use auk::*;
+//! use auk::aria::{AriaLive, Role};
+//! use auk::renderer::HtmlElementRenderer;
+//!
+//! let dialog = div()
+//!     .role(Role::Dialog)
+//!     .aria_modal(true)
+//!     .aria_labelledby("dialog-title")
+//!     .child(p().aria_live(AriaLive::Polite).child("Saved."));
+//!
+//! assert_eq!(
+//!     HtmlElementRenderer::new().render_to_string(&dialog).unwrap(),
+//!     r#"<div role="dialog" aria-modal="true" aria-labelledby="dialog-title"><p aria-live="polite">Saved.</p></div>"#
+//! );
+//! ```
+
+use std::fmt;
+
+use crate::{AttrValue, HtmlElement};
+
+macro_rules! aria_tokens {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                #[doc = concat!("`", $value, "`")]
+                $variant,
+            )*
+        }
+
+        impl $name {
+            /// Returns the attribute value for this token.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $value,)*
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl AttrValue for $name {
+            fn into_attr_value(self) -> Option<String> {
+                Some(self.as_str().to_string())
+            }
+        }
+    };
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal),* $(,)? } $custom:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                #[doc = concat!("`", $value, "`")]
+                $variant,
+            )*
+            /// A value that does not have a variant, which is used verbatim.
+            $custom(String),
+        }
+
+        impl $name {
+            /// Returns the attribute value for this token.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)*
+                    Self::$custom(value) => value,
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl AttrValue for $name {
+            fn into_attr_value(self) -> Option<String> {
+                match self {
+                    Self::$custom(value) => Some(value),
+                    token => Some(token.as_str().to_string()),
+                }
+            }
+        }
+    };
+}
+
+aria_tokens!(
+    /// A non-abstract [WAI-ARIA role](https://www.w3.org/TR/wai-aria-1.2/#role_definitions).
+    ///
+    /// Roles from other specifications (e.g., the `doc-*` roles from
+    /// [DPUB-ARIA](https://www.w3.org/TR/dpub-aria-1.1/)) can be set using
+    /// [`Role::Custom`].
+    Role {
+        Alert => "alert",
+        AlertDialog => "alertdialog",
+        Application => "application",
+        Article => "article",
+        Banner => "banner",
+        Blockquote => "blockquote",
+        Button => "button",
+        Caption => "caption",
+        Cell => "cell",
+        Checkbox => "checkbox",
+        Code => "code",
+        ColumnHeader => "columnheader",
+        Combobox => "combobox",
+        Complementary => "complementary",
+        ContentInfo => "contentinfo",
+        Definition => "definition",
+        Deletion => "deletion",
+        Dialog => "dialog",
+        Document => "document",
+        Emphasis => "emphasis",
+        Feed => "feed",
+        Figure => "figure",
+        Form => "form",
+        Generic => "generic",
+        Grid => "grid",
+        GridCell => "gridcell",
+        Group => "group",
+        Heading => "heading",
+        Img => "img",
+        Insertion => "insertion",
+        Link => "link",
+        List => "list",
+        Listbox => "listbox",
+        ListItem => "listitem",
+        Log => "log",
+        Main => "main",
+        Marquee => "marquee",
+        Math => "math",
+        Menu => "menu",
+        Menubar => "menubar",
+        MenuItem => "menuitem",
+        MenuItemCheckbox => "menuitemcheckbox",
+        MenuItemRadio => "menuitemradio",
+        Meter => "meter",
+        Navigation => "navigation",
+        None => "none",
+        Note => "note",
+        Option => "option",
+        Paragraph => "paragraph",
+        Presentation => "presentation",
+        ProgressBar => "progressbar",
+        Radio => "radio",
+        RadioGroup => "radiogroup",
+        Region => "region",
+        Row => "row",
+        RowGroup => "rowgroup",
+        RowHeader => "rowheader",
+        Scrollbar => "scrollbar",
+        Search => "search",
+        Searchbox => "searchbox",
+        Separator => "separator",
+        Slider => "slider",
+        SpinButton => "spinbutton",
+        Status => "status",
+        Strong => "strong",
+        Subscript => "subscript",
+        Superscript => "superscript",
+        Switch => "switch",
+        Tab => "tab",
+        Table => "table",
+        TabList => "tablist",
+        TabPanel => "tabpanel",
+        Term => "term",
+        Textbox => "textbox",
+        Time => "time",
+        Timer => "timer",
+        Toolbar => "toolbar",
+        Tooltip => "tooltip",
+        Tree => "tree",
+        TreeGrid => "treegrid",
+        TreeItem => "treeitem",
+    }
+    Custom
+);
+
+aria_tokens!(
+    /// A value for the [`aria-autocomplete`](https://www.w3.org/TR/wai-aria-1.2/#aria-autocomplete) attribute.
+    AriaAutocomplete {
+        Inline => "inline",
+        List => "list",
+        Both => "both",
+        None => "none",
+    }
+);
+
+aria_tokens!(
+    /// A value for the [`aria-checked`](https://www.w3.org/TR/wai-aria-1.2/#aria-checked) attribute.
+    AriaChecked {
+        True => "true",
+        False => "false",
+        Mixed => "mixed",
+    }
+);
+
+aria_tokens!(
+    /// A value for the [`aria-current`](https://www.w3.org/TR/wai-aria-1.2/#aria-current) attribute.
+    AriaCurrent {
+        Page => "page",
+        Step => "step",
+        Location => "location",
+        Date => "date",
+        Time => "time",
+        True => "true",
+        False => "false",
+    }
+);
+
+aria_tokens!(
+    /// A value for the [`aria-haspopup`](https://www.w3.org/TR/wai-aria-1.2/#aria-haspopup) attribute.
+    AriaHasPopup {
+        False => "false",
+        True => "true",
+        Menu => "menu",
+        Listbox => "listbox",
+        Tree => "tree",
+        Grid => "grid",
+        Dialog => "dialog",
+    }
+);
+
+aria_tokens!(
+    /// A value for the [`aria-invalid`](https://www.w3.org/TR/wai-aria-1.2/#aria-invalid) attribute.
+    AriaInvalid {
+        Grammar => "grammar",
+        False => "false",
+        Spelling => "spelling",
+        True => "true",
+    }
+);
+
+aria_tokens!(
+    /// A value for the [`aria-live`](https://www.w3.org/TR/wai-aria-1.2/#aria-live) attribute.
+    AriaLive {
+        Assertive => "assertive",
+        Off => "off",
+        Polite => "polite",
+    }
+);
+
+aria_tokens!(
+    /// A value for the [`aria-orientation`](https://www.w3.org/TR/wai-aria-1.2/#aria-orientation) attribute.
+    AriaOrientation {
+        Horizontal => "horizontal",
+        Vertical => "vertical",
+    }
+);
+
+aria_tokens!(
+    /// A value for the [`aria-pressed`](https://www.w3.org/TR/wai-aria-1.2/#aria-pressed) attribute.
+    AriaPressed {
+        True => "true",
+        False => "false",
+        Mixed => "mixed",
+    }
+);
+
+aria_tokens!(
+    /// A token for the [`aria-relevant`](https://www.w3.org/TR/wai-aria-1.2/#aria-relevant) attribute.
+    AriaRelevant {
+        Additions => "additions",
+        All => "all",
+        Removals => "removals",
+        Text => "text",
+    }
+);
+
+aria_tokens!(
+    /// A value for the [`aria-sort`](https://www.w3.org/TR/wai-aria-1.2/#aria-sort) attribute.
+    AriaSort {
+        Ascending => "ascending",
+        Descending => "descending",
+        None => "none",
+        Other => "other",
+    }
+);
+
+macro_rules! aria_attribute_methods {
+    ($($name:ident => $attr_name:literal: $value:ty),* $(,)?) => {
+        $(
+            #[doc = concat!("Sets the [`", $attr_name, "`](https://www.w3.org/TR/wai-aria-1.2/#", $attr_name, ") attribute to the provided value.")]
+            pub fn $name(self, value: impl Into<Option<$value>>) -> Self {
+                let value: Option<$value> = value.into();
+                self.attr($attr_name, value.map(|value| value.to_string()))
+            }
+        )*
+    };
+}
+
+macro_rules! aria_string_attribute_methods {
+    ($($name:ident => $attr_name:literal),* $(,)?) => {
+        $(
+            #[doc = concat!("Sets the [`", $attr_name, "`](https://www.w3.org/TR/wai-aria-1.2/#", $attr_name, ") attribute to the provided value.")]
+            pub fn $name(self, value: impl AttrValue) -> Self {
+                self.attr($attr_name, value)
+            }
+        )*
+    };
+}
+
+impl HtmlElement {
+    /// Sets the [`role`](https://www.w3.org/TR/wai-aria-1.2/#introroles) attribute to the provided value.
+    pub fn role(self, value: impl Into<Option<Role>>) -> Self {
+        let value: Option<Role> = value.into();
+        self.attr("role", value)
+    }
+
+    /// Sets the [`role`](https://www.w3.org/TR/wai-aria-1.2/#introroles) attribute to the provided roles, in order of preference.
+    ///
+    /// The attribute is removed if no roles are provided.
+    pub fn roles(self, values: impl IntoIterator<Item = Role>) -> Self {
+        let values = values
+            .into_iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>();
+
+        self.attr("role", (!values.is_empty()).then(|| values.join(" ")))
+    }
+
+    /// Sets the [`aria-relevant`](https://www.w3.org/TR/wai-aria-1.2/#aria-relevant) attribute to the provided values.
+    ///
+    /// The attribute is removed if no values are provided.
+    pub fn aria_relevant(self, values: impl IntoIterator<Item = AriaRelevant>) -> Self {
+        let values = values
+            .into_iter()
+            .map(|value| value.as_str())
+            .collect::<Vec<_>>();
+
+        self.attr(
+            "aria-relevant",
+            (!values.is_empty()).then(|| values.join(" ")),
+        )
+    }
+
+    // Attributes that take a string or a space-separated list of IDs.
+    aria_string_attribute_methods!(
+        aria_activedescendant => "aria-activedescendant",
+        aria_controls => "aria-controls",
+        aria_describedby => "aria-describedby",
+        aria_details => "aria-details",
+        aria_errormessage => "aria-errormessage",
+        aria_flowto => "aria-flowto",
+        aria_keyshortcuts => "aria-keyshortcuts",
+        aria_label => "aria-label",
+        aria_labelledby => "aria-labelledby",
+        aria_owns => "aria-owns",
+        aria_placeholder => "aria-placeholder",
+        aria_roledescription => "aria-roledescription",
+        aria_valuetext => "aria-valuetext",
+    );
+
+    aria_attribute_methods!(
+        aria_atomic => "aria-atomic": bool,
+        aria_autocomplete => "aria-autocomplete": AriaAutocomplete,
+        aria_busy => "aria-busy": bool,
+        aria_checked => "aria-checked": AriaChecked,
+        aria_colcount => "aria-colcount": i32,
+        aria_colindex => "aria-colindex": u32,
+        aria_colspan => "aria-colspan": u32,
+        aria_current => "aria-current": AriaCurrent,
+        aria_disabled => "aria-disabled": bool,
+        aria_expanded => "aria-expanded": bool,
+        aria_haspopup => "aria-haspopup": AriaHasPopup,
+        aria_hidden => "aria-hidden": bool,
+        aria_invalid => "aria-invalid": AriaInvalid,
+        aria_level => "aria-level": u32,
+        aria_live => "aria-live": AriaLive,
+        aria_modal => "aria-modal": bool,
+        aria_multiline => "aria-multiline": bool,
+        aria_multiselectable => "aria-multiselectable": bool,
+        aria_orientation => "aria-orientation": AriaOrientation,
+        aria_posinset => "aria-posinset": u32,
+        aria_pressed => "aria-pressed": AriaPressed,
+        aria_readonly => "aria-readonly": bool,
+        aria_required => "aria-required": bool,
+        aria_rowcount => "aria-rowcount": i32,
+        aria_rowindex => "aria-rowindex": u32,
+        aria_rowspan => "aria-rowspan": u32,
+        aria_selected => "aria-selected": bool,
+        aria_setsize => "aria-setsize": i32,
+        aria_sort => "aria-sort": AriaSort,
+        aria_valuemax => "aria-valuemax": f64,
+        aria_valuemin => "aria-valuemin": f64,
+        aria_valuenow => "aria-valuenow": f64,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::renderer::HtmlElementRenderer;
+    use crate::*;
+
+    use super::*;
+
+    fn render_to_string(element: &HtmlElement) -> String {
+        HtmlElementRenderer::new()
+            .render_to_string(element)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_aria_attributes() {
+        insta::assert_yaml_snapshot!(render_to_string(
+            &div()
+                .role(Role::TabList)
+                .aria_orientation(AriaOrientation::Horizontal)
+                .child(
+                    button()
+                        .role(Role::Tab)
+                        .aria_selected(true)
+                        .aria_controls("panel-1")
+                        .aria_haspopup(AriaHasPopup::Menu)
+                        .aria_checked(AriaChecked::Mixed)
+                )
+                .child(
+                    div()
+                        .role(Role::ProgressBar)
+                        .aria_valuemin(0.0)
+                        .aria_valuemax(100.0)
+                        .aria_valuenow(42.5)
+                        .aria_setsize(-1)
+                        .aria_relevant([AriaRelevant::Additions, AriaRelevant::Text])
+                )
+        ));
+    }
+
+    #[test]
+    fn test_custom_and_fallback_roles() {
+        assert_eq!(
+            render_to_string(&section().role(Role::Custom("doc-chapter".to_string()))),
+            r#"<section role="doc-chapter"></section>"#
+        );
+        assert_eq!(
+            render_to_string(&button().roles([Role::Switch, Role::Checkbox])),
+            r#"<button role="switch checkbox"></button>"#
+        );
+    }
+
+    #[test]
+    fn test_aria_attributes_can_be_removed() {
+        let element = div()
+            .role(Role::Alert)
+            .aria_hidden(true)
+            .aria_label("Label")
+            .aria_relevant([AriaRelevant::All])
+            .roles([Role::Switch, Role::Checkbox])
+            .role(None)
+            .aria_hidden(None)
+            .aria_label(None::<&str>)
+            .aria_relevant([]);
+
+        assert!(element.attrs.is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+use std::borrow::Cow;
+
+/// A value that can be set as the value of an attribute.
+///
+/// Converting a value returns `None` when the attribute should be absent
+/// instead, which allows boolean attributes to be written as `bool`s:
+///
+/// ```
+/// use auk::*;
+/// use auk::renderer::HtmlElementRenderer;
+///
+/// let element = input().type_("number").min(1).step(0.5).required(true).disabled(false);
+///
+/// assert_eq!(
+///     HtmlElementRenderer::new().render_to_string(&element).unwrap(),
+///     r#"<input type="number" min="1" step="0.5" required>"#
+/// );
+/// ```
+///
+/// Implement this trait for your own types (e.g., enums of keywords) to be
+/// able to pass them to attribute methods directly.
+pub trait AttrValue {
+    /// Converts this value into the value of an attribute, or `None` if the
+    /// attribute should be absent.
+    fn into_attr_value(self) -> Option<String>;
+}
+
+impl AttrValue for String {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self)
+    }
+}
+
+impl AttrValue for &str {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl AttrValue for &String {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.clone())
+    }
+}
+
+impl AttrValue for Cow<'_, str> {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.into_owned())
+    }
+}
+
+impl AttrValue for Box<str> {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.into_string())
+    }
+}
+
+impl AttrValue for char {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+/// Boolean attributes are present when `true` and absent when `false`.
+impl AttrValue for bool {
+    fn into_attr_value(self) -> Option<String> {
+        self.then(String::new)
+    }
+}
+
+impl<T: AttrValue> AttrValue for Option<T> {
+    fn into_attr_value(self) -> Option<String> {
+        self.and_then(AttrValue::into_attr_value)
+    }
+}
+
+macro_rules! impl_attr_value_for_numbers {
+    ($($ty:ty),*) => {
+        $(
+            impl AttrValue for $ty {
+                fn into_attr_value(self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_attr_value_for_numbers!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attr_values() {
+        assert_eq!("a".into_attr_value(), Some("a".to_string()));
+        assert_eq!(true.into_attr_value(), Some(String::new()));
+        assert_eq!(false.into_attr_value(), None);
+        assert_eq!((-3).into_attr_value(), Some("-3".to_string()));
+        assert_eq!(2.5.into_attr_value(), Some("2.5".to_string()));
+        assert_eq!(1.0.into_attr_value(), Some("1".to_string()));
+        assert_eq!(Some(42usize).into_attr_value(), Some("42".to_string()));
+        assert_eq!(Some(false).into_attr_value(), None);
+        assert_eq!(None::<&str>.into_attr_value(), None);
+    }
+}
@@ -0,0 +1,672 @@
+//! Constructs for computing and applying the differences between two trees of
+//! [`HtmlElement`]s.
+//!
+//! A [`Patch`] is a list of [`PatchOp`]s that, when applied in order to the old
+//! tree, transform it into the new tree. Each operation addresses a node by its
+//! path: the list of child indices leading from the root to the node, as the
+//! tree exists at the point the operation is applied.
+//!
+//! Children are matched positionally by default. Children that have a key
+//! attribute (`data-key`, by default) are matched by key instead, allowing
+//! reordered lists to be expressed as moves rather than as rewrites.
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+use crate::{Element, HtmlElement, TextElement};
+
+/// The attribute used to key children by default.
+pub const DEFAULT_KEY_ATTRIBUTE: &str = "data-key";
+
+/// A single operation in a [`Patch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "op", rename_all = "snake_case"))]
+pub enum PatchOp {
+    /// Sets an attribute on the element at `path`.
+    ///
+    /// An attribute that is already present keeps its position, otherwise it
+    /// is added after the existing attributes.
+    SetAttribute {
+        /// The path to the element.
+        path: Vec<usize>,
+        /// The name of the attribute.
+        name: String,
+        /// The value of the attribute.
+        value: String,
+    },
+
+    /// Removes an attribute from the element at `path`.
+    RemoveAttribute {
+        /// The path to the element.
+        path: Vec<usize>,
+        /// The name of the attribute.
+        name: String,
+    },
+
+    /// Inserts a child into the element at `path`, at the given index.
+    InsertChild {
+        /// The path to the parent element.
+        path: Vec<usize>,
+        /// The index to insert the child at.
+        index: usize,
+        /// The child to insert.
+        node: Element,
+    },
+
+    /// Removes the child at the given index from the element at `path`.
+    RemoveChild {
+        /// The path to the parent element.
+        path: Vec<usize>,
+        /// The index of the child to remove.
+        index: usize,
+    },
+
+    /// Moves a child of the element at `path` from one index to another.
+    ///
+    /// The child is removed from `from` before being inserted at `to`.
+    MoveChild {
+        /// The path to the parent element.
+        path: Vec<usize>,
+        /// The current index of the child.
+        from: usize,
+        /// The index to move the child to.
+        to: usize,
+    },
+
+    /// Replaces the text of the text node at `path`.
+    ReplaceText {
+        /// The path to the text node.
+        path: Vec<usize>,
+        /// The new text.
+        text: String,
+    },
+
+    /// Replaces the node at `path` with another node.
+    Replace {
+        /// The path to the node.
+        path: Vec<usize>,
+        /// The node to replace it with.
+        node: Element,
+    },
+}
+
+/// An error that occurred while applying a [`Patch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    /// The path does not lead to a node of the kind the operation expects.
+    InvalidPath(Vec<usize>),
+
+    /// The index is out of bounds for the children of the element at the path.
+    InvalidIndex {
+        /// The path to the parent element.
+        path: Vec<usize>,
+        /// The index that is out of bounds.
+        index: usize,
+    },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPath(path) => write!(f, "invalid path: {path:?}"),
+            Self::InvalidIndex { path, index } => {
+                write!(f, "invalid index {index} for children at path {path:?}")
+            }
+        }
+    }
+}
+
+impl Error for PatchError {}
+
+/// A list of operations that transform one tree of [`HtmlElement`]s into
+/// another.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Patch {
+    /// The operations in this patch, in the order they must be applied.
+    pub ops: Vec<PatchOp>,
+}
+
+impl Patch {
+    /// Returns whether this patch contains no operations.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Applies this patch to the given [`HtmlElement`].
+    ///
+    /// If an operation fails the element is left with all of the preceding
+    /// operations applied.
+    pub fn apply(&self, element: &mut HtmlElement) -> Result<(), PatchError> {
+        for op in &self.ops {
+            apply_op(element, op)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the [`Patch`] that transforms `old` into `new`, keying children by
+/// [`DEFAULT_KEY_ATTRIBUTE`].
+pub fn diff(old: &HtmlElement, new: &HtmlElement) -> Patch {
+    Differ::new().diff(old, new)
+}
+
+/// Computes the differences between trees of [`HtmlElement`]s.
+#[derive(Debug, Clone)]
+pub struct Differ {
+    key_attribute: String,
+}
+
+impl Default for Differ {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Differ {
+    /// Returns a new [`Differ`].
+    pub fn new() -> Self {
+        Self {
+            key_attribute: DEFAULT_KEY_ATTRIBUTE.to_string(),
+        }
+    }
+
+    /// Sets the attribute used to key children.
+    pub fn key_attribute(mut self, key_attribute: impl Into<String>) -> Self {
+        self.key_attribute = key_attribute.into();
+        self
+    }
+
+    /// Returns the [`Patch`] that transforms `old` into `new`.
+    pub fn diff(&self, old: &HtmlElement, new: &HtmlElement) -> Patch {
+        let mut ops = Vec::new();
+        self.diff_element(old, new, &mut Vec::new(), &mut ops);
+
+        Patch { ops }
+    }
+
+    fn key<'a>(&self, element: &'a Element) -> Option<&'a str> {
+        match element {
+            Element::Html(element) => element.attrs.get(&self.key_attribute).map(String::as_str),
+            _ => None,
+        }
+    }
+
+    fn diff_node(
+        &self,
+        old: &Element,
+        new: &Element,
+        path: &mut Vec<usize>,
+        ops: &mut Vec<PatchOp>,
+    ) {
+        match (old, new) {
+            (Element::Html(old), Element::Html(new)) => self.diff_element(old, new, path, ops),
+            (
+                Element::Text(TextElement {
+                    text: old_text,
+                    safe: old_safe,
+                }),
+                Element::Text(TextElement {
+                    text: new_text,
+                    safe: new_safe,
+                }),
+            ) if old_safe == new_safe => {
+                if old_text != new_text {
+                    ops.push(PatchOp::ReplaceText {
+                        path: path.clone(),
+                        text: new_text.clone(),
+                    });
+                }
+            }
+            _ if old == new => {}
+            _ => ops.push(PatchOp::Replace {
+                path: path.clone(),
+                node: new.clone(),
+            }),
+        }
+    }
+
+    fn diff_element(
+        &self,
+        old: &HtmlElement,
+        new: &HtmlElement,
+        path: &mut Vec<usize>,
+        ops: &mut Vec<PatchOp>,
+    ) {
+        if old.tag_name != new.tag_name {
+            ops.push(PatchOp::Replace {
+                path: path.clone(),
+                node: Element::Html(new.clone()),
+            });
+            return;
+        }
+
+        // Attributes that are already in the right order can be set in place.
+        // Setting an attribute that is not present appends it, so everything
+        // after the first attribute that is out of order is removed and set
+        // again in order.
+        let in_place = new
+            .attrs
+            .keys()
+            .zip(
+                old.attrs
+                    .keys()
+                    .filter(|name| new.attrs.contains_key(*name)),
+            )
+            .take_while(|(new_name, old_name)| new_name == old_name)
+            .count();
+
+        for name in old.attrs.keys() {
+            if !matches!(new.attrs.get_index_of(name), Some(index) if index < in_place) {
+                ops.push(PatchOp::RemoveAttribute {
+                    path: path.clone(),
+                    name: name.clone(),
+                });
+            }
+        }
+
+        for (index, (name, value)) in new.attrs.iter().enumerate() {
+            if index >= in_place || old.attrs.get(name) != Some(value) {
+                ops.push(PatchOp::SetAttribute {
+                    path: path.clone(),
+                    name: name.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+
+        self.diff_children(&old.children, &new.children, path, ops);
+    }
+
+    fn diff_children(
+        &self,
+        old: &[Element],
+        new: &[Element],
+        path: &mut Vec<usize>,
+        ops: &mut Vec<PatchOp>,
+    ) {
+        // Match each new child with its counterpart among the old children:
+        // keyed children by key, and unkeyed children by their order.
+        let mut keyed = HashMap::<&str, VecDeque<usize>>::new();
+        let mut unkeyed = VecDeque::new();
+        for (index, child) in old.iter().enumerate() {
+            match self.key(child) {
+                Some(key) => keyed.entry(key).or_default().push_back(index),
+                None => unkeyed.push_back(index),
+            }
+        }
+
+        let counterparts = new
+            .iter()
+            .map(|child| match self.key(child) {
+                Some(key) => keyed.get_mut(key).and_then(VecDeque::pop_front),
+                None => unkeyed.pop_front(),
+            })
+            .collect::<Vec<_>>();
+
+        let mut is_matched = vec![false; old.len()];
+        for &old_index in counterparts.iter().flatten() {
+            is_matched[old_index] = true;
+        }
+
+        // Remove the old children without a counterpart, starting from the end
+        // so that the indices of the remaining children are unaffected.
+        for index in (0..old.len()).rev() {
+            if !is_matched[index] {
+                ops.push(PatchOp::RemoveChild {
+                    path: path.clone(),
+                    index,
+                });
+            }
+        }
+
+        // The remaining children in their current order, identified by the
+        // index they will have in `new`.
+        let mut new_indices = vec![None; old.len()];
+        for (index, old_index) in counterparts.iter().enumerate() {
+            if let Some(old_index) = old_index {
+                new_indices[*old_index] = Some(index);
+            }
+        }
+        let mut current = new_indices.into_iter().flatten().collect::<Vec<_>>();
+
+        // The children that form the longest run that is already in order can
+        // stay where they are, and everything else is moved around them.
+        let is_stable = longest_increasing_subsequence(&counterparts);
+
+        // Place the children from the end, moving or inserting each one before
+        // the child that follows it.
+        let mut anchor = None;
+        for (index, child) in new.iter().enumerate().rev() {
+            let position_of = |current: &[usize], index| {
+                current
+                    .iter()
+                    .position(|&current| current == index)
+                    .expect("child is present")
+            };
+            let anchor_position = |current: &[usize]| {
+                anchor.map_or(current.len(), |anchor| position_of(current, anchor))
+            };
+
+            if counterparts[index].is_some() {
+                if !is_stable[index] {
+                    let from = position_of(&current, index);
+                    current.remove(from);
+                    let to = anchor_position(&current);
+                    current.insert(to, index);
+
+                    if from != to {
+                        ops.push(PatchOp::MoveChild {
+                            path: path.clone(),
+                            from,
+                            to,
+                        });
+                    }
+                }
+            } else {
+                let to = anchor_position(&current);
+                current.insert(to, index);
+
+                ops.push(PatchOp::InsertChild {
+                    path: path.clone(),
+                    index: to,
+                    node: child.clone(),
+                });
+            }
+
+            anchor = Some(index);
+        }
+
+        for (index, old_index) in counterparts.into_iter().enumerate() {
+            if let Some(old_index) = old_index {
+                path.push(index);
+                self.diff_node(&old[old_index], &new[index], path, ops);
+                path.pop();
+            }
+        }
+    }
+}
+
+/// Returns, for each item, whether it is part of the longest strictly
+/// increasing subsequence of the present items.
+fn longest_increasing_subsequence(items: &[Option<usize>]) -> Vec<bool> {
+    // `tails[length]` is the index of the smallest item that ends an
+    // increasing subsequence of `length + 1` items.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut predecessors = vec![None; items.len()];
+
+    for (index, item) in items.iter().enumerate() {
+        let Some(item) = item else {
+            continue;
+        };
+
+        let length = tails.partition_point(|&tail| items[tail] < Some(*item));
+        predecessors[index] = length.checked_sub(1).map(|previous| tails[previous]);
+
+        if length == tails.len() {
+            tails.push(index);
+        } else {
+            tails[length] = index;
+        }
+    }
+
+    let mut is_stable = vec![false; items.len()];
+    let mut next = tails.last().copied();
+    while let Some(index) = next {
+        is_stable[index] = true;
+        next = predecessors[index];
+    }
+
+    is_stable
+}
+
+fn apply_op(root: &mut HtmlElement, op: &PatchOp) -> Result<(), PatchError> {
+    match op {
+        PatchOp::SetAttribute { path, name, value } => {
+            element_at(root, path)?
+                .attrs
+                .insert(name.clone(), value.clone());
+        }
+        PatchOp::RemoveAttribute { path, name } => {
+            element_at(root, path)?.attrs.shift_remove(name);
+        }
+        PatchOp::InsertChild { path, index, node } => {
+            let children = &mut element_at(root, path)?.children;
+            if *index > children.len() {
+                return Err(invalid_index(path, *index));
+            }
+
+            children.insert(*index, node.clone());
+        }
+        PatchOp::RemoveChild { path, index } => {
+            let children = &mut element_at(root, path)?.children;
+            if *index >= children.len() {
+                return Err(invalid_index(path, *index));
+            }
+
+            children.remove(*index);
+        }
+        PatchOp::MoveChild { path, from, to } => {
+            let children = &mut element_at(root, path)?.children;
+            if *from >= children.len() {
+                return Err(invalid_index(path, *from));
+            }
+            if *to >= children.len() {
+                return Err(invalid_index(path, *to));
+            }
+
+            let child = children.remove(*from);
+            children.insert(*to, child);
+        }
+        PatchOp::ReplaceText { path, text } => match node_at(root, path)? {
+            Element::Text(node) => node.text.clone_from(text),
+            _ => return Err(PatchError::InvalidPath(path.clone())),
+        },
+        PatchOp::Replace { path, node } => {
+            if path.is_empty() {
+                let Element::Html(element) = node else {
+                    return Err(PatchError::InvalidPath(path.clone()));
+                };
+
+                *root = element.clone();
+            } else {
+                *node_at(root, path)? = node.clone();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn invalid_index(path: &[usize], index: usize) -> PatchError {
+    PatchError::InvalidIndex {
+        path: path.to_vec(),
+        index,
+    }
+}
+
+/// Returns the node at the given (non-empty) path.
+fn node_at<'a>(root: &'a mut HtmlElement, path: &[usize]) -> Result<&'a mut Element, PatchError> {
+    let invalid_path = || PatchError::InvalidPath(path.to_vec());
+
+    let (&last, parent_path) = path.split_last().ok_or_else(invalid_path)?;
+
+    element_at(root, parent_path)
+        .map_err(|_| invalid_path())?
+        .children
+        .get_mut(last)
+        .ok_or_else(invalid_path)
+}
+
+/// Returns the element at the given path.
+fn element_at<'a>(
+    root: &'a mut HtmlElement,
+    path: &[usize],
+) -> Result<&'a mut HtmlElement, PatchError> {
+    let mut element = root;
+    for &index in path {
+        element = match element.children.get_mut(index) {
+            Some(Element::Html(child)) => child,
+            _ => return Err(PatchError::InvalidPath(path.to_vec())),
+        };
+    }
+
+    Ok(element)
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_yaml_snapshot;
+
+    use crate::renderer::HtmlElementRenderer;
+    use crate::*;
+
+    use super::*;
+
+    fn assert_round_trip(old: &HtmlElement, new: &HtmlElement) -> Patch {
+        let patch = diff(old, new);
+
+        let mut patched = old.clone();
+        patch.apply(&mut patched).unwrap();
+        assert_eq!(&patched, new);
+
+        // Attributes are compared without regard to their order, so compare
+        // the rendered HTML as well.
+        assert_eq!(
+            HtmlElementRenderer::new()
+                .render_to_string(&patched)
+                .unwrap(),
+            HtmlElementRenderer::new().render_to_string(new).unwrap()
+        );
+
+        patch
+    }
+
+    fn list(keys: &[&str]) -> HtmlElement {
+        ul().children(
+            keys.iter()
+                .map(|key| li().attr("data-key", *key).child(key.to_uppercase())),
+        )
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let element = div().class("card").child(p().child("Hello"));
+
+        assert!(assert_round_trip(&element, &element.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_attributes_and_text() {
+        let old = div()
+            .class("card")
+            .id("old")
+            .child(h1().child("Title"))
+            .child(p().child("Hello"));
+        let new = div()
+            .class("card active")
+            .child(h1().child("Title"))
+            .child(p().child("Goodbye"));
+
+        assert_yaml_snapshot!(assert_round_trip(&old, &new));
+    }
+
+    #[test]
+    fn test_diff_preserves_attribute_order() {
+        let old = a()
+            .href("/")
+            .class("link")
+            .title("Home")
+            .attr("data-id", "1");
+        let new = a()
+            .href("/home")
+            .attr("rel", "home")
+            .class("link")
+            .attr("data-id", "1")
+            .title("Home");
+
+        assert_yaml_snapshot!(assert_round_trip(&old, &new));
+    }
+
+    #[test]
+    fn test_diff_unkeyed_children() {
+        let old = div()
+            .child(p().child("One"))
+            .child(p().child("Two"))
+            .child(p().child("Three"));
+        let new = div()
+            .child(p().child("One"))
+            .child(h2().child("Two"))
+            .child(CommentElement::new(" Three "))
+            .child(p().child("Four"));
+
+        assert_yaml_snapshot!(assert_round_trip(&old, &new));
+    }
+
+    #[test]
+    fn test_diff_keyed_children() {
+        let old = list(&["a", "b", "c", "d", "e"]);
+        let new = list(&["b", "c", "d", "e", "a"]);
+        let patch = assert_round_trip(&old, &new);
+        assert_eq!(
+            patch.ops,
+            vec![PatchOp::MoveChild {
+                path: vec![],
+                from: 0,
+                to: 4,
+            }]
+        );
+
+        let old = list(&["a", "b", "c", "d"]);
+        let new = list(&["d", "x", "b", "a"]);
+
+        assert_yaml_snapshot!(assert_round_trip(&old, &new));
+    }
+
+    #[test]
+    fn test_diff_custom_key_attribute() {
+        let old = ul()
+            .child(li().id("one").child("One"))
+            .child(li().id("two").child("Two"));
+        let new = ul()
+            .child(li().id("two").child("Two"))
+            .child(li().id("one").child("One"));
+
+        let patch = Differ::new().key_attribute("id").diff(&old, &new);
+        assert_eq!(patch.ops.len(), 1);
+
+        let mut patched = old.clone();
+        patch.apply(&mut patched).unwrap();
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn test_diff_replace_root() {
+        let old = div().child("Hello");
+        let new = section().child("Hello");
+
+        assert_yaml_snapshot!(assert_round_trip(&old, &new));
+    }
+
+    #[test]
+    fn test_apply_invalid_path() {
+        let mut element = div().child("Hello");
+
+        let patch = Patch {
+            ops: vec![PatchOp::SetAttribute {
+                path: vec![0],
+                name: "class".to_string(),
+                value: "text".to_string(),
+            }],
+        };
+
+        assert_eq!(
+            patch.apply(&mut element),
+            Err(PatchError::InvalidPath(vec![0]))
+        );
+    }
+}
@@ -1,13 +1,55 @@
-use crate::{HtmlElement, TextElement};
+use crate::{
+    CDataElement, CommentElement, DoctypeElement, HtmlElement, ProcessingInstructionElement,
+    TextElement,
+};
 
 /// A DOM element.
-#[derive(Debug, Clone)]
+///
+/// # Serialization
+///
+/// With the `serde` feature enabled, elements can be serialized and
+/// deserialized. Each element is represented as an object with a `type` field
+/// indicating the kind of element, alongside the fields of that element:
+///
+/// ```json
+/// { "type": "html", "tag_name": "a", "attrs": { "href": "/" }, "children": [] }
+/// { "type": "text", "text": "Hello, world!", "safe": false }
+/// { "type": "comment", "text": "A comment" }
+/// { "type": "doctype", "name": "html", "public_id": null, "system_id": null }
+/// { "type": "processing_instruction", "target": "xml-stylesheet", "data": "href=\"style.xsl\"" }
+/// { "type": "cdata", "text": "x < y" }
+/// ```
+///
+/// The `attrs` and `children` of an `html` element may be omitted, in which
+/// case they are empty. Attributes retain their order.
+///
+/// The `safe` flag of a `text` element is ignored when deserializing, so
+/// deserialized text is always escaped when rendered. This keeps a tree
+/// deserialized from untrusted input from writing arbitrary HTML through
+/// `"safe": true`. Deserialize a `TrustedElement` instead to keep the flag
+/// for trees that come from a trusted source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
 pub enum Element {
     /// A text element.
     Text(TextElement),
 
     /// An HTML element.
     Html(HtmlElement),
+
+    /// A comment.
+    Comment(CommentElement),
+
+    /// A document type declaration.
+    Doctype(DoctypeElement),
+
+    /// A processing instruction.
+    ProcessingInstruction(ProcessingInstructionElement),
+
+    /// A CDATA section.
+    #[cfg_attr(feature = "serde", serde(rename = "cdata"))]
+    CData(CDataElement),
 }
 
 impl Element {
@@ -15,7 +57,7 @@ impl Element {
     pub fn text(self) -> Option<TextElement> {
         match self {
             Self::Text(text) => Some(text),
-            Self::Html(_) => None,
+            _ => None,
         }
     }
 
@@ -23,7 +65,15 @@ impl Element {
     pub fn html(self) -> Option<HtmlElement> {
         match self {
             Self::Html(html) => Some(html),
-            Self::Text(_) => None,
+            _ => None,
+        }
+    }
+
+    /// Converts this [`Element`] into a [`CommentElement`].
+    pub fn comment(self) -> Option<CommentElement> {
+        match self {
+            Self::Comment(comment) => Some(comment),
+            _ => None,
         }
     }
 }
@@ -34,6 +84,30 @@ impl From<TextElement> for Element {
     }
 }
 
+impl From<CommentElement> for Element {
+    fn from(value: CommentElement) -> Self {
+        Self::Comment(value)
+    }
+}
+
+impl From<DoctypeElement> for Element {
+    fn from(value: DoctypeElement) -> Self {
+        Self::Doctype(value)
+    }
+}
+
+impl From<ProcessingInstructionElement> for Element {
+    fn from(value: ProcessingInstructionElement) -> Self {
+        Self::ProcessingInstruction(value)
+    }
+}
+
+impl From<CDataElement> for Element {
+    fn from(value: CDataElement) -> Self {
+        Self::CData(value)
+    }
+}
+
 impl From<String> for Element {
     fn from(value: String) -> Self {
         Self::Text(TextElement::from(value))
@@ -57,3 +131,204 @@ impl From<HtmlElement> for Element {
         Self::Html(value)
     }
 }
+
+/// An [`Element`] deserialized from a trusted source.
+///
+/// Unlike deserializing an [`Element`], this keeps the `safe` flag of each
+/// `text` element, which means that the text is written verbatim when
+/// rendered.
+///
+/// **Only deserialize a [`TrustedElement`] from input that you control (e.g.,
+/// a tree you serialized yourself). Doing so with untrusted input makes it
+/// trivial to introduce an
+/// [XSS](https://en.wikipedia.org/wiki/Cross-site_scripting) vulnerability.**
+///
+/// ```
+/// use auk::*;
+///
+/// let json = r#"{ "type": "text", "text": "<b>bold</b>", "safe": true }"#;
+///
+/// let element: Element = serde_json::from_str(json).unwrap();
+/// assert!(!element.text().unwrap().safe);
+///
+/// let TrustedElement(element) = serde_json::from_str(json).unwrap();
+/// assert!(element.text().unwrap().safe);
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedElement(pub Element);
+
+#[cfg(feature = "serde")]
+impl From<TrustedElement> for Element {
+    fn from(value: TrustedElement) -> Self {
+        value.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TrustedElement {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        trusted::TrustedNode::deserialize(deserializer).map(|node| Self(node.into()))
+    }
+}
+
+/// Mirrors of the serialized representation of [`Element`]s that keep the
+/// `safe` flag of text.
+#[cfg(feature = "serde")]
+mod trusted {
+    use indexmap::IndexMap;
+    use serde::Deserialize;
+
+    use crate::{
+        CDataElement, CommentElement, DoctypeElement, Element, HtmlElement,
+        ProcessingInstructionElement, TextElement,
+    };
+
+    #[derive(Deserialize)]
+    #[serde(tag = "type", rename_all = "snake_case")]
+    pub(super) enum TrustedNode {
+        Text(TrustedText),
+        Html(TrustedHtml),
+        Comment(CommentElement),
+        Doctype(DoctypeElement),
+        ProcessingInstruction(ProcessingInstructionElement),
+        #[serde(rename = "cdata")]
+        CData(CDataElement),
+    }
+
+    #[derive(Deserialize)]
+    pub(super) struct TrustedText {
+        text: String,
+        #[serde(default)]
+        safe: bool,
+    }
+
+    #[derive(Deserialize)]
+    pub(super) struct TrustedHtml {
+        tag_name: String,
+        #[serde(default)]
+        attrs: IndexMap<String, String>,
+        #[serde(default)]
+        children: Vec<TrustedNode>,
+    }
+
+    impl From<TrustedNode> for Element {
+        fn from(node: TrustedNode) -> Self {
+            match node {
+                TrustedNode::Text(TrustedText { text, safe }) => {
+                    Element::Text(TextElement { text, safe })
+                }
+                TrustedNode::Html(TrustedHtml {
+                    tag_name,
+                    attrs,
+                    children,
+                }) => Element::Html(HtmlElement {
+                    tag_name,
+                    attrs,
+                    children: children.into_iter().map(Element::from).collect(),
+                }),
+                TrustedNode::Comment(comment) => Element::Comment(comment),
+                TrustedNode::Doctype(doctype) => Element::Doctype(doctype),
+                TrustedNode::ProcessingInstruction(pi) => Element::ProcessingInstruction(pi),
+                TrustedNode::CData(cdata) => Element::CData(cdata),
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::renderer::HtmlElementRenderer;
+    use crate::*;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize() {
+        let element: Element = div()
+            .class("card")
+            .id("main")
+            .child(Element::Comment(CommentElement::new("A comment")))
+            .child("Hello, world!")
+            .into();
+
+        insta::assert_snapshot!(serde_json::to_string_pretty(&element).unwrap());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let element: Element = html()
+            .child(Element::Doctype(DoctypeElement::html()))
+            .child(
+                body()
+                    .attr("data-z", "1")
+                    .attr("data-a", "2")
+                    .attr("data-m", "3")
+                    .child(TextElement::safe_static("<b>bold</b>"))
+                    .child(Element::ProcessingInstruction(
+                        ProcessingInstructionElement::new("xml-stylesheet", "href=\"style.xsl\""),
+                    ))
+                    .child(Element::CData(CDataElement::new("x < y"))),
+            )
+            .into();
+
+        let json = serde_json::to_string(&element).unwrap();
+        let TrustedElement(deserialized) = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, element);
+
+        let body = deserialized.html().unwrap().children[1]
+            .clone()
+            .html()
+            .unwrap();
+        assert_eq!(
+            body.attrs.keys().collect::<Vec<_>>(),
+            ["data-z", "data-a", "data-m"]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_defaults() {
+        let element: Element = serde_json::from_str(
+            r#"{ "type": "html", "tag_name": "p", "children": [{ "type": "text", "text": "<script>" }] }"#,
+        )
+        .unwrap();
+
+        let element = element.html().unwrap();
+        assert!(element.attrs.is_empty());
+
+        let text = element.children[0].clone().text().unwrap();
+        assert_eq!(text.text, "<script>");
+        assert!(!text.safe);
+    }
+
+    #[test]
+    fn test_deserialize_ignores_safe() {
+        let json = r#"{ "type": "html", "tag_name": "p", "children": [{ "type": "text", "text": "<script>", "safe": true }] }"#;
+
+        let element: Element = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            HtmlElementRenderer::new()
+                .render_to_string(&element.html().unwrap())
+                .unwrap(),
+            "<p>&lt;script&gt;</p>"
+        );
+
+        let TrustedElement(element) = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            HtmlElementRenderer::new()
+                .render_to_string(&element.html().unwrap())
+                .unwrap(),
+            "<p><script></p>"
+        );
+    }
+
+    #[test]
+    fn test_fragment_is_a_list_of_elements() {
+        let fragment = fragment().child(p().child("One")).child("Two");
+
+        let json = serde_json::to_value(&fragment).unwrap();
+        assert!(json.is_array());
+        assert_eq!(serde_json::from_value::<Fragment>(json).unwrap(), fragment);
+    }
+}
@@ -0,0 +1,101 @@
+use std::{iter, vec};
+
+use crate::{Element, Render, With, WithChildren};
+
+/// A list of sibling [`Element`]s that do not share a parent element.
+///
+/// When a [`Fragment`] is added as a child of an element its children are
+/// added in its place, rather than being wrapped in another element.
+///
+/// [`Fragment`] implements `Render<Fragment>` rather than rendering into a
+/// single [`HtmlElement`](crate::HtmlElement), so that its children are not
+/// wrapped. Render a [`Fragment`] directly using
+/// [`HtmlElementRenderer::render_fragment`](crate::renderer::HtmlElementRenderer::render_fragment).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Fragment {
+    /// The child nodes of this fragment.
+    pub children: Vec<Element>,
+}
+
+impl Fragment {
+    /// Returns a new, empty [`Fragment`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Returns a new, empty [`Fragment`].
+pub fn fragment() -> Fragment {
+    Fragment::new()
+}
+
+impl With for Fragment {}
+
+impl WithChildren for Fragment {
+    #[inline(always)]
+    fn extend(&mut self, children: impl IntoIterator<Item = Element>) {
+        self.children.extend(children)
+    }
+}
+
+impl Render<Fragment> for Fragment {
+    fn render(self) -> impl Into<Fragment> {
+        self
+    }
+}
+
+impl From<Vec<Element>> for Fragment {
+    fn from(value: Vec<Element>) -> Self {
+        Self { children: value }
+    }
+}
+
+impl FromIterator<Element> for Fragment {
+    fn from_iter<T: IntoIterator<Item = Element>>(iter: T) -> Self {
+        Self {
+            children: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for Fragment {
+    type Item = Element;
+    type IntoIter = vec::IntoIter<Element>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.children.into_iter()
+    }
+}
+
+/// A trait for values that can be added as children of an element.
+///
+/// This is implemented for everything that can be converted into an
+/// [`Element`], as well as for [`Fragment`]s, which add each of their
+/// children individually.
+pub trait IntoChildren {
+    /// The iterator over the children.
+    type IntoIter: Iterator<Item = Element>;
+
+    /// Converts this value into the children to add to an element.
+    fn into_children(self) -> Self::IntoIter;
+}
+
+impl<T: Into<Element>> IntoChildren for T {
+    type IntoIter = iter::Once<Element>;
+
+    #[inline(always)]
+    fn into_children(self) -> Self::IntoIter {
+        iter::once(self.into())
+    }
+}
+
+impl IntoChildren for Fragment {
+    type IntoIter = vec::IntoIter<Element>;
+
+    #[inline(always)]
+    fn into_children(self) -> Self::IntoIter {
+        self.children.into_iter()
+    }
+}
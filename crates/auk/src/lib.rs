@@ -1,26 +1,46 @@
 #![doc = include_str!("../README.md")]
 #![deny(missing_docs)]
 
+pub mod aria;
+mod attr_value;
+pub mod diff;
 mod element;
+mod fragment;
+pub mod mathml;
+#[cfg(feature = "parser")]
+pub mod parser;
 pub mod renderer;
+pub mod sanitizer;
+pub mod selector;
+pub mod style;
+pub mod svg;
+pub mod url;
 pub mod visitor;
 
-use std::iter;
+use std::collections::HashSet;
 
 use indexmap::IndexMap;
 
+pub use crate::attr_value::*;
 pub use crate::element::*;
+pub use crate::fragment::*;
+
+#[cfg(feature = "macros")]
+pub use auk_macros::{html, Component};
 
 /// An HTML element.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HtmlElement {
     /// The tag name for this element.
     pub tag_name: String,
 
     /// The attributes of this element.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub attrs: IndexMap<String, String>,
 
     /// The child nodes of this element.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub children: Vec<Element>,
 }
 
@@ -36,32 +56,192 @@ impl HtmlElement {
 
     /// Returns whether this element is a [void element](https://developer.mozilla.org/en-US/docs/Glossary/Void_element).
     pub fn is_void(&self) -> bool {
-        match self.tag_name.as_str() {
-            "area" | "base" | "br" | "col" | "embed" | "hr" | "img" | "input" | "link" | "meta"
-            | "param" | "source" | "track" | "wbr" => true,
-            _ => false,
-        }
+        matches!(
+            self.tag_name.as_str(),
+            "area"
+                | "base"
+                | "br"
+                | "col"
+                | "embed"
+                | "hr"
+                | "img"
+                | "input"
+                | "link"
+                | "meta"
+                | "param"
+                | "source"
+                | "track"
+                | "wbr"
+        )
+    }
+
+    /// Returns whether this element is a [raw text element](https://html.spec.whatwg.org/multipage/syntax.html#raw-text-elements).
+    ///
+    /// The text inside of raw text elements is not escaped when rendered.
+    pub fn is_raw_text(&self) -> bool {
+        matches!(
+            self.tag_name.as_str(),
+            "script" | "style" | "xmp" | "iframe" | "noembed" | "noframes" | "plaintext"
+        )
+    }
+
+    /// Returns whether this element is an [escapable raw text element](https://html.spec.whatwg.org/multipage/syntax.html#escapable-raw-text-elements).
+    pub fn is_escapable_raw_text(&self) -> bool {
+        matches!(self.tag_name.as_str(), "textarea" | "title")
     }
 
     /// Sets the specified attribute on this element.
     ///
     /// Will overwrite the existing value for the attribute, if one exists.
-    pub fn attr<V>(mut self, name: impl Into<String>, value: impl Into<Option<V>>) -> Self
-    where
-        V: Into<String>,
-    {
+    /// Values that convert to `None` (e.g., `false`) remove the attribute instead.
+    pub fn attr(mut self, name: impl Into<String>, value: impl AttrValue) -> Self {
         let name = name.into();
-        match value.into() {
-            Some(id) => {
-                *self.attrs.entry(name).or_default() = id.into();
+        match value.into_attr_value() {
+            Some(value) => {
+                *self.attrs.entry(name).or_default() = value;
             }
             None => {
-                self.attrs.remove(&name);
+                self.attrs.shift_remove(&name);
             }
         }
 
         self
     }
+
+    /// Sets the specified [custom data attribute](https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/data-*)
+    /// on this element.
+    ///
+    /// The name is given without the `data-` prefix (e.g., `data("domain", ..)`
+    /// sets the `data-domain` attribute).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is empty, contains uppercase ASCII letters, or would
+    /// not make a valid attribute name.
+    ///
+    /// To set the `data` attribute of an `<object>`, use [`data_`](Self::data_).
+    pub fn data(self, name: impl AsRef<str>, value: impl AttrValue) -> Self {
+        let name = name.as_ref();
+        let attr_name = format!("data-{name}");
+        if name.is_empty()
+            || name.chars().any(|char| char.is_ascii_uppercase())
+            || !renderer::is_valid_attribute_name(&attr_name)
+        {
+            panic!("invalid custom data attribute name: {name:?}");
+        }
+
+        self.attr(attr_name, value)
+    }
+
+    /// Sets the `data` attribute to the provided value.
+    ///
+    /// This is the address of the resource of an `<object>`. It has a trailing
+    /// underscore, as [`data`](Self::data) sets custom data attributes.
+    pub fn data_(self, value: impl AttrValue) -> Self {
+        self.attr("data", value)
+    }
+
+    /// Adds the given class to this element, if it does not already have it.
+    ///
+    /// Unlike [`HtmlElement::class`], this does not overwrite any existing
+    /// classes. The given class may contain multiple whitespace-separated class
+    /// names (e.g., the classes from a style builder), each of which will be added.
+    ///
+    /// The order of the classes is preserved, and duplicates are removed.
+    pub fn add_class(mut self, class: impl Into<String>) -> Self {
+        let class = class.into();
+        self.update_classes(|classes| {
+            classes.extend(class.split_ascii_whitespace().map(ToOwned::to_owned))
+        });
+        self
+    }
+
+    /// Removes the given class from this element.
+    ///
+    /// The given class may contain multiple whitespace-separated class names,
+    /// each of which will be removed. The `class` attribute is removed once
+    /// there are no classes left.
+    pub fn remove_class(mut self, class: impl Into<String>) -> Self {
+        let class = class.into();
+        let removed = class.split_ascii_whitespace().collect::<Vec<_>>();
+        self.update_classes(|classes| classes.retain(|class| !removed.contains(&class.as_str())));
+        self
+    }
+
+    /// Toggles the given class on this element, adding it if it is not present
+    /// and removing it if it is.
+    ///
+    /// The given class may contain multiple whitespace-separated class names,
+    /// each of which will be toggled.
+    pub fn toggle_class(mut self, class: impl Into<String>) -> Self {
+        let class = class.into();
+        self.update_classes(|classes| {
+            for class in class.split_ascii_whitespace() {
+                match classes.iter().position(|existing| existing == class) {
+                    Some(index) => {
+                        classes.remove(index);
+                    }
+                    None => classes.push(class.to_owned()),
+                }
+            }
+        });
+        self
+    }
+
+    /// Merges the given [`Style`](style::Style) into the `style` attribute of
+    /// this element.
+    ///
+    /// Unlike [`HtmlElement::style`], this does not overwrite the existing
+    /// declarations. Where both set the same property, the value from the
+    /// given style is used.
+    pub fn add_style(mut self, style: style::Style) -> Self {
+        let style = self
+            .attrs
+            .get("style")
+            .map(|existing| style::Style::from_css(existing))
+            .unwrap_or_default()
+            .merge(style);
+
+        if style.is_empty() {
+            self.attrs.shift_remove("style");
+        } else {
+            *self.attrs.entry("style".to_string()).or_default() = style.to_string();
+        }
+
+        self
+    }
+
+    /// Returns whether this element has the given class.
+    pub fn has_class(&self, class: &str) -> bool {
+        self.attrs
+            .get("class")
+            .is_some_and(|classes| classes.split_ascii_whitespace().any(|name| name == class))
+    }
+
+    /// Updates the list of classes in the `class` attribute of this element.
+    fn update_classes(&mut self, f: impl FnOnce(&mut Vec<String>)) {
+        let mut classes = self
+            .attrs
+            .get("class")
+            .map(|classes| {
+                classes
+                    .split_ascii_whitespace()
+                    .map(ToOwned::to_owned)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        f(&mut classes);
+
+        let mut seen = HashSet::new();
+        classes.retain(|class| seen.insert(class.clone()));
+
+        if classes.is_empty() {
+            self.attrs.shift_remove("class");
+        } else {
+            *self.attrs.entry("class".to_string()).or_default() = classes.join(" ");
+        }
+    }
 }
 
 /// A trait for elements that can be modified.
@@ -84,20 +264,22 @@ pub trait WithChildren {
     fn extend(&mut self, children: impl IntoIterator<Item = Element>);
 
     /// Adds a new child element to this element.
-    fn child(mut self, child: impl Into<Element>) -> Self
+    ///
+    /// Adding a [`Fragment`] will add each of its children.
+    fn child(mut self, child: impl IntoChildren) -> Self
     where
         Self: Sized,
     {
-        self.extend(iter::once(child.into()));
+        self.extend(child.into_children());
         self
     }
 
     /// Adds the specified child elements to this element.
-    fn children(mut self, children: impl IntoIterator<Item = impl Into<Element>>) -> Self
+    fn children(mut self, children: impl IntoIterator<Item = impl IntoChildren>) -> Self
     where
         Self: Sized,
     {
-        self.extend(children.into_iter().map(Into::into));
+        self.extend(children.into_iter().flat_map(IntoChildren::into_children));
         self
     }
 }
@@ -110,7 +292,8 @@ impl WithChildren for HtmlElement {
 }
 
 /// A text element.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextElement {
     /// The text content of this element.
     pub text: String,
@@ -122,6 +305,10 @@ pub struct TextElement {
     /// escaped. Failure to do so makes it trivial to introduce an
     /// [XSS](https://en.wikipedia.org/wiki/Cross-site_scripting)
     /// vulnerability.**
+    ///
+    /// This is ignored when deserializing, unless deserializing a
+    /// `TrustedElement`.
+    #[cfg_attr(feature = "serde", serde(skip_deserializing))]
     pub safe: bool,
 }
 
@@ -149,82 +336,304 @@ impl<T: Into<String>> From<T> for TextElement {
     }
 }
 
+/// A comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommentElement {
+    /// The text content of this comment.
+    pub text: String,
+}
+
+impl CommentElement {
+    /// Returns a new [`CommentElement`] with the given text.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into() }
+    }
+}
+
+/// A [document type declaration](https://developer.mozilla.org/en-US/docs/Glossary/Doctype).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DoctypeElement {
+    /// The name of the document type (e.g., `html`).
+    pub name: String,
+
+    /// The public identifier of the document type.
+    pub public_id: Option<String>,
+
+    /// The system identifier of the document type.
+    pub system_id: Option<String>,
+}
+
+impl DoctypeElement {
+    /// Returns a new [`DoctypeElement`] with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            public_id: None,
+            system_id: None,
+        }
+    }
+
+    /// Returns the HTML5 [`DoctypeElement`] (`<!DOCTYPE html>`).
+    pub fn html() -> Self {
+        Self::new("html")
+    }
+}
+
+/// A processing instruction (e.g., `<?xml-stylesheet href="style.xsl"?>`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessingInstructionElement {
+    /// The target of this processing instruction.
+    pub target: String,
+
+    /// The data of this processing instruction.
+    pub data: String,
+}
+
+impl ProcessingInstructionElement {
+    /// Returns a new [`ProcessingInstructionElement`] with the given target and data.
+    pub fn new(target: impl Into<String>, data: impl Into<String>) -> Self {
+        Self {
+            target: target.into(),
+            data: data.into(),
+        }
+    }
+}
+
+/// A CDATA section.
+///
+/// CDATA sections are only valid inside of foreign elements (e.g., `<svg>`) and in XML.
+/// Elsewhere in HTML, [`HtmlElementRenderer`](crate::renderer::HtmlElementRenderer) writes
+/// their text as escaped text instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CDataElement {
+    /// The text content of this CDATA section.
+    pub text: String,
+}
+
+impl CDataElement {
+    /// Returns a new [`CDataElement`] with the given text.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into() }
+    }
+}
+
+/// Creates a method for each of the given attributes.
+///
+/// The names of the attributes are also collected into the given constant,
+/// which is used to check them against the HTML Standard.
 macro_rules! create_attribute_methods {
-    ($($name:ident),*) => {
+    ($names:ident: $($name:ident $(=> $attr_name:literal)?),* $(,)?) => {
+        #[cfg(test)]
+        const $names: &'static [&'static str] = &[
+            $(create_attribute_methods!(@name $name $(=> $attr_name)?)),*
+        ];
+
         $(
-            #[doc = concat!("Sets the `", stringify!($name), "` attribute to the provided value.")]
-            pub fn $name<V>(self, value: impl Into<Option<V>>) -> Self
-            where
-                V: Into<String>,
-            {
-                self.attr(stringify!($name), value)
+            create_attribute_methods!(@method $name $(=> $attr_name)?);
+        )*
+    };
+    (@name $name:ident) => {
+        stringify!($name)
+    };
+    (@name $name:ident => $attr_name:literal) => {
+        $attr_name
+    };
+    (@method $name:ident) => {
+        create_attribute_methods!(@method $name => stringify!($name));
+    };
+    (@method $name:ident => $attr_name:expr) => {
+        #[doc = concat!("Sets the `", $attr_name, "` attribute to the provided value.")]
+        pub fn $name(self, value: impl AttrValue) -> Self {
+            self.attr($attr_name, value)
+        }
+    };
+}
+
+macro_rules! create_enumerated_attribute_methods {
+    ($names:ident: $($name:ident => ($true_value:literal, $false_value:literal)),* $(,)?) => {
+        #[cfg(test)]
+        const $names: &'static [&'static str] = &[$(stringify!($name)),*];
+
+        $(
+            #[doc = concat!("Sets the `", stringify!($name), "` attribute to `", $true_value, "` or `", $false_value, "`.")]
+            ///
+            /// Unlike a boolean attribute, `false` does not remove the attribute.
+            /// Pass `None` to remove it.
+            pub fn $name(self, value: impl Into<Option<bool>>) -> Self {
+                let value: Option<bool> = value.into();
+                self.attr(
+                    stringify!($name),
+                    value.map(|value| if value { $true_value } else { $false_value }),
+                )
             }
         )*
-    }
+    };
 }
 
 impl HtmlElement {
+    // Global attributes.
+    // https://html.spec.whatwg.org/multipage/dom.html#global-attributes
+    create_attribute_methods!(
+        GLOBAL_ATTRIBUTES:
+        accesskey,
+        autocapitalize,
+        autofocus,
+        class,
+        dir,
+        enterkeyhint,
+        hidden,
+        id,
+        inert,
+        inputmode,
+        is,
+        itemid,
+        itemprop,
+        itemref,
+        itemscope,
+        itemtype,
+        lang,
+        nonce,
+        popover,
+        slot,
+        style,
+        tabindex,
+        title,
+    );
+
+    // Enumerated attributes with a keyword for each of `true` and `false`.
+    create_enumerated_attribute_methods!(
+        ENUMERATED_ATTRIBUTES:
+        autocorrect => ("on", "off"),
+        contenteditable => ("true", "false"),
+        draggable => ("true", "false"),
+        spellcheck => ("true", "false"),
+        translate => ("yes", "no"),
+        writingsuggestions => ("true", "false"),
+    );
+
+    // Element-specific attributes.
+    // https://html.spec.whatwg.org/multipage/indices.html#attributes-3
+    //
+    // The `data` attribute of `<object>` is set using `data_`, as `data` sets
+    // custom data attributes.
     create_attribute_methods!(
+        ELEMENT_ATTRIBUTES:
+        abbr,
+        accept,
+        accept_charset => "accept-charset",
         action,
+        allow,
+        allowfullscreen,
+        alpha,
         alt,
+        as_ => "as",
+        async_ => "async",
+        autocomplete,
+        autoplay,
+        blocking,
         charset,
         checked,
-        class,
+        cite,
+        closedby,
+        color,
+        colorspace,
+        cols,
+        colspan,
+        command,
+        commandfor,
         content,
+        controls,
+        coords,
         crossorigin,
+        datetime,
+        decoding,
+        default,
         defer,
+        dirname,
+        disabled,
+        download,
+        enctype,
+        fetchpriority,
+        for_ => "for",
+        form,
+        formaction,
+        formenctype,
+        formmethod,
+        formnovalidate,
+        formtarget,
+        headers,
+        height,
+        high,
         href,
-        id,
+        hreflang,
+        http_equiv => "http-equiv",
+        imagesizes,
+        imagesrcset,
         integrity,
-        lang,
+        ismap,
+        kind,
+        label,
+        list,
         loading,
+        loop_ => "loop",
+        low,
         max,
+        maxlength,
+        media,
         method,
+        min,
+        minlength,
+        multiple,
+        muted,
         name,
+        nomodule,
+        novalidate,
+        open,
+        optimum,
+        pattern,
+        ping,
         placeholder,
+        playsinline,
+        popovertarget,
+        popovertargetaction,
+        poster,
+        preload,
+        readonly,
+        referrerpolicy,
         rel,
-        role,
+        required,
+        reversed,
+        rows,
+        rowspan,
+        sandbox,
+        scope,
+        selected,
+        shadowrootclonable,
+        shadowrootcustomelementregistry,
+        shadowrootdelegatesfocus,
+        shadowrootmode,
+        shadowrootserializable,
+        shape,
+        size,
+        sizes,
+        span,
         src,
+        srcdoc,
+        srclang,
+        srcset,
         start,
-        style,
-        tabindex,
+        step,
         target,
-        title,
-        translate,
-        value
+        type_ => "type",
+        usemap,
+        value,
+        width,
+        wrap,
     );
-
-    /// Sets the `async` attribute to the provided value.
-    pub fn async_<V>(self, value: impl Into<Option<V>>) -> Self
-    where
-        V: Into<String>,
-    {
-        self.attr("async", value)
-    }
-
-    /// Sets the `for` attribute to the provided value.
-    pub fn for_<V>(self, value: impl Into<Option<V>>) -> Self
-    where
-        V: Into<String>,
-    {
-        self.attr("for", value)
-    }
-
-    /// Sets the `http-equiv` attribute to the provided value.
-    pub fn http_equiv<V>(self, value: impl Into<Option<V>>) -> Self
-    where
-        V: Into<String>,
-    {
-        self.attr("http-equiv", value)
-    }
-
-    /// Sets the `type` attribute to the provided value.
-    pub fn type_<V>(self, value: impl Into<Option<V>>) -> Self
-    where
-        V: Into<String>,
-    {
-        self.attr("type", value)
-    }
 }
 
 macro_rules! html_elements {
@@ -250,9 +659,46 @@ html_elements!(
 );
 
 /// A trait for types that can be rendered as [`HtmlElement`]s.
-pub trait Render {
-    /// Renders this value into an [`HtmlElement`].
-    fn render(self) -> impl Into<HtmlElement>;
+///
+/// Types that render into a single [`HtmlElement`] (the default `Output`) can
+/// be converted into an [`HtmlElement`] or an [`Element`], which means they
+/// can be added as children directly.
+///
+/// Types with multiple root nodes can render into a [`Fragment`] instead, by
+/// implementing `Render<Fragment>`:
+///
+/// ```
+/// use auk::*;
+/// use auk::renderer::HtmlElementRenderer;
+///
+/// struct Definition {
+///     term: String,
+///     description: String,
+/// }
+///
+/// impl Render<Fragment> for Definition {
+///     fn render(self) -> impl Into<Fragment> {
+///         fragment()
+///             .child(dt().child(self.term))
+///             .child(dd().child(self.description))
+///     }
+/// }
+///
+/// let definition: Fragment = Definition {
+///     term: "Auk".to_string(),
+///     description: "A seabird.".to_string(),
+/// }
+/// .render()
+/// .into();
+///
+/// assert_eq!(
+///     HtmlElementRenderer::new().render_to_string(&dl().child(definition)).unwrap(),
+///     "<dl><dt>Auk</dt><dd>A seabird.</dd></dl>"
+/// );
+/// ```
+pub trait Render<Output = HtmlElement> {
+    /// Renders this value into its `Output`.
+    fn render(self) -> impl Into<Output>;
 }
 
 impl<T: Render> From<T> for HtmlElement {
@@ -267,6 +713,48 @@ impl<T: Render> From<T> for Element {
     }
 }
 
+/// Items used by the code generated by `auk_macros`. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    use crate::Render;
+
+    /// Constructs components that do not derive `Component` using [`Default`].
+    ///
+    /// Derived components have an inherent `__auk_props` function, which
+    /// takes precedence over this one.
+    pub trait DefaultComponent: Default {
+        /// Returns the default component, which is its own props builder.
+        fn __auk_props() -> Self {
+            Self::default()
+        }
+    }
+
+    impl<T: Default> DefaultComponent for T {}
+
+    /// Builds a component from its props builder.
+    #[diagnostic::on_unimplemented(
+        message = "missing required props",
+        label = "missing required props",
+        note = "every prop without a default needs to be set"
+    )]
+    pub trait BuildComponent {
+        /// The component that is built.
+        type Component;
+
+        /// Builds the component.
+        fn build(self) -> Self::Component;
+    }
+
+    #[diagnostic::do_not_recommend]
+    impl<T: Render> BuildComponent for T {
+        type Component = T;
+
+        fn build(self) -> Self::Component {
+            self
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::renderer::HtmlElementRenderer;
@@ -294,6 +782,87 @@ mod tests {
         assert_eq!(element.attrs.get("bar"), Some(&"b".to_string()));
     }
 
+    #[test]
+    fn test_removing_attributes_keeps_attribute_order() {
+        let element = input()
+            .type_("checkbox")
+            .name("agree")
+            .checked(true)
+            .id("agree")
+            .name(None::<&str>);
+
+        assert_eq!(
+            element.attrs.keys().collect::<Vec<_>>(),
+            ["type", "checked", "id"]
+        );
+    }
+
+    #[test]
+    fn test_enumerated_attributes() {
+        let element = div()
+            .contenteditable(true)
+            .draggable(false)
+            .spellcheck(false)
+            .translate(false)
+            .autocorrect(true)
+            .writingsuggestions(false);
+
+        assert_eq!(
+            render_to_string(&element),
+            r#"<div contenteditable="true" draggable="false" spellcheck="false" translate="no" autocorrect="on" writingsuggestions="false"></div>"#
+        );
+
+        let element = element.draggable(None).translate(None);
+
+        assert!(!element.attrs.contains_key("draggable"));
+        assert!(!element.attrs.contains_key("translate"));
+    }
+
+    #[test]
+    fn test_class_list() {
+        let element = div()
+            .class("card card")
+            .add_class("shadow")
+            .add_class("card rounded  shadow")
+            .toggle_class("active");
+
+        assert_eq!(
+            element.attrs.get("class"),
+            Some(&"card shadow rounded active".to_string())
+        );
+        assert!(element.has_class("rounded"));
+        assert!(!element.has_class("round"));
+
+        let element = element
+            .toggle_class("active hidden")
+            .remove_class("card shadow");
+
+        assert_eq!(
+            element.attrs.get("class"),
+            Some(&"rounded hidden".to_string())
+        );
+        assert!(!element.has_class("active"));
+    }
+
+    #[test]
+    fn test_class_list_keeps_attribute_order() {
+        let element = a()
+            .href("/")
+            .add_class("link")
+            .id("home")
+            .add_class("active");
+
+        assert_eq!(
+            element.attrs.keys().collect::<Vec<_>>(),
+            ["href", "class", "id"]
+        );
+
+        let element = element.remove_class("link active");
+
+        assert!(!element.attrs.contains_key("class"));
+        assert_eq!(element.attrs.keys().collect::<Vec<_>>(), ["href", "id"]);
+    }
+
     #[test]
     fn test_render_to_string() {
         let element = div().class("outer").child(
@@ -305,6 +874,18 @@ mod tests {
         insta::assert_yaml_snapshot!(render_to_string(&element));
     }
 
+    #[test]
+    fn test_fragment_children_are_flattened() {
+        let items = fragment().child(li().child("One")).child(li().child("Two"));
+
+        let element = ul()
+            .child(items.clone())
+            .children([items, fragment().child(li().child("Three"))]);
+
+        assert_eq!(element.children.len(), 5);
+        insta::assert_yaml_snapshot!(render_to_string(&element));
+    }
+
     #[test]
     fn test_doctype_auto_insertion() {
         insta::assert_yaml_snapshot!(render_to_string(&html()));
@@ -323,8 +904,8 @@ mod tests {
     fn test_empty_attributes() {
         insta::assert_yaml_snapshot!(render_to_string(
             &script()
-                .async_("")
-                .defer("")
+                .async_(true)
+                .defer(true)
                 .attr("data-domain", "example.com")
                 .src("https://plausible.io/js/plausible.js"),
         ));
@@ -340,6 +921,86 @@ mod tests {
         ))
     }
 
+    #[test]
+    fn test_attribute_names() {
+        insta::assert_yaml_snapshot!(render_to_string(
+            &form()
+                .accept_charset("utf-8")
+                .child(label().for_("email").child("Email"))
+                .child(
+                    input()
+                        .type_("email")
+                        .id("email")
+                        .autocomplete("email")
+                        .required(true)
+                )
+                .child(video().loop_(true).width(640).height(360))
+                .child(
+                    link()
+                        .as_("font")
+                        .rel("preload")
+                        .referrerpolicy("no-referrer")
+                )
+        ));
+    }
+
+    #[test]
+    fn test_attribute_methods_cover_the_html_standard() {
+        let standard = include_str!("whatwg_attributes.txt")
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect::<HashSet<_>>();
+
+        let methods = HtmlElement::GLOBAL_ATTRIBUTES
+            .iter()
+            .chain(HtmlElement::ENUMERATED_ATTRIBUTES)
+            .chain(HtmlElement::ELEMENT_ATTRIBUTES)
+            // Set using `data_`.
+            .chain(&["data"])
+            .copied()
+            .collect::<HashSet<_>>();
+
+        let mut missing = standard.difference(&methods).collect::<Vec<_>>();
+        missing.sort();
+        assert!(missing.is_empty(), "missing attribute methods: {missing:?}");
+
+        let mut unknown = methods.difference(&standard).collect::<Vec<_>>();
+        unknown.sort();
+        assert!(unknown.is_empty(), "unknown attribute methods: {unknown:?}");
+    }
+
+    #[test]
+    fn test_object_data_attribute() {
+        let element = object()
+            .data_("movie.mp4")
+            .type_("video/mp4")
+            .data("id", "1");
+
+        assert_eq!(
+            render_to_string(&element),
+            r#"<object data="movie.mp4" type="video/mp4" data-id="1"></object>"#
+        );
+    }
+
+    #[test]
+    fn test_data_attributes() {
+        let element = div()
+            .data("domain", "example.com")
+            .data("user-id", "1")
+            .data("domain", None::<&str>);
+
+        assert_eq!(
+            render_to_string(&element),
+            r#"<div data-user-id="1"></div>"#
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid custom data attribute name")]
+    fn test_data_attributes_reject_invalid_names() {
+        let _ = div().data("userId", "1");
+    }
+
     #[test]
     fn test_escape_html_in_body_text() {
         insta::assert_yaml_snapshot!(render_to_string(
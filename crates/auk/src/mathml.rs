@@ -0,0 +1,112 @@
+//! Constructors for [MathML](https://developer.mozilla.org/en-US/docs/Web/MathML) elements.
+//!
+//! ```
+//! use auk::WithChildren;
+//! use auk::renderer::HtmlElementRenderer;
+//! use auk::mathml::{self, MathMlAttributes};
+//!
+//! let formula = mathml::math().display("block").child(
+//!     mathml::msup()
+//!         .child(mathml::mi().child("x"))
+//!         .child(mathml::mn().child("2")),
+//! );
+//!
+//! assert_eq!(
+//!     HtmlElementRenderer::new().render_to_string(&formula).unwrap(),
+//!     r#"<math display="block"><msup><mi>x</mi><mn>2</mn></msup></math>"#
+//! );
+//! ```
+
+use crate::{AttrValue, HtmlElement};
+
+macro_rules! mathml_elements {
+    ($($name:ident => $tag_name:literal),* $(,)?) => {
+        $(
+            #[doc = concat!("[`<", $tag_name, ">`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/", $tag_name, ")")]
+            pub fn $name() -> HtmlElement {
+                HtmlElement::new($tag_name)
+            }
+        )*
+    }
+}
+
+mathml_elements!(
+    annotation => "annotation",
+    annotation_xml => "annotation-xml",
+    maction => "maction",
+    math => "math",
+    merror => "merror",
+    mfrac => "mfrac",
+    mi => "mi",
+    mmultiscripts => "mmultiscripts",
+    mn => "mn",
+    mo => "mo",
+    mover => "mover",
+    mpadded => "mpadded",
+    mphantom => "mphantom",
+    mprescripts => "mprescripts",
+    mroot => "mroot",
+    mrow => "mrow",
+    ms => "ms",
+    mspace => "mspace",
+    msqrt => "msqrt",
+    mstyle => "mstyle",
+    msub => "msub",
+    msubsup => "msubsup",
+    msup => "msup",
+    mtable => "mtable",
+    mtd => "mtd",
+    mtext => "mtext",
+    mtr => "mtr",
+    munder => "munder",
+    munderover => "munderover",
+    semantics => "semantics",
+);
+
+macro_rules! mathml_attributes {
+    ($($name:ident => $attr_name:literal),* $(,)?) => {
+        /// Methods for setting [MathML attributes](https://developer.mozilla.org/en-US/docs/Web/MathML/Global_attributes)
+        /// on an [`HtmlElement`].
+        pub trait MathMlAttributes: Sized {
+            $(
+                #[doc = concat!("Sets the `", $attr_name, "` attribute to the provided value.")]
+                fn $name(self, value: impl AttrValue) -> Self;
+            )*
+        }
+
+        impl MathMlAttributes for HtmlElement {
+            $(
+                fn $name(self, value: impl AttrValue) -> Self {
+                    self.attr($attr_name, value)
+                }
+            )*
+        }
+    }
+}
+
+mathml_attributes!(
+    accent => "accent",
+    accentunder => "accentunder",
+    columnspan => "columnspan",
+    depth => "depth",
+    display => "display",
+    displaystyle => "displaystyle",
+    encoding => "encoding",
+    fence => "fence",
+    largeop => "largeop",
+    linethickness => "linethickness",
+    lspace => "lspace",
+    mathbackground => "mathbackground",
+    mathcolor => "mathcolor",
+    mathsize => "mathsize",
+    mathvariant => "mathvariant",
+    maxsize => "maxsize",
+    minsize => "minsize",
+    movablelimits => "movablelimits",
+    rspace => "rspace",
+    scriptlevel => "scriptlevel",
+    separator => "separator",
+    stretchy => "stretchy",
+    symmetric => "symmetric",
+    voffset => "voffset",
+);
@@ -0,0 +1,398 @@
+//! Constructs for parsing HTML into [`HtmlElement`]s.
+//!
+//! Parsing is performed by [`html5ever`], which implements the parsing
+//! algorithm from the [HTML Standard](https://html.spec.whatwg.org/multipage/parsing.html).
+//! This means that malformed input is recovered from the same way a browser
+//! would: implied end tags are inserted, void elements never receive children,
+//! and character references are decoded.
+
+use std::borrow::Cow;
+use std::cell::{Ref, RefCell};
+
+use html5ever::interface::{ElementFlags, NodeOrText, QuirksMode, TreeSink};
+use html5ever::tendril::{StrTendril, TendrilSink};
+use html5ever::{local_name, namespace_url, ns, Attribute, QualName};
+
+use crate::{CommentElement, Element, HtmlElement, TextElement};
+
+/// Parses the given HTML document into an [`HtmlElement`].
+///
+/// The returned element is always the root `<html>` element, as the parser
+/// will insert any missing `<html>`, `<head>`, or `<body>` elements.
+pub fn parse_document(html: &str) -> HtmlElement {
+    let sink = html5ever::parse_document(TreeBuilder::new(), Default::default()).one(html);
+
+    sink.into_document()
+}
+
+/// Parses the given HTML fragment into a list of [`Element`]s.
+///
+/// The fragment is parsed as if it were the contents of a `<body>` element.
+pub fn parse_fragment(html: &str) -> Vec<Element> {
+    let sink = html5ever::parse_fragment(
+        TreeBuilder::new(),
+        Default::default(),
+        QualName::new(None, ns!(html), local_name!("body")),
+        Vec::new(),
+    )
+    .one(html);
+
+    sink.into_fragment()
+}
+
+type Handle = usize;
+
+const DOCUMENT: Handle = 0;
+
+enum NodeData {
+    Document,
+    Element {
+        name: QualName,
+        attrs: Vec<Attribute>,
+    },
+    Text(StrTendril),
+    Comment(StrTendril),
+    /// A node that has no representation as an [`Element`].
+    Ignored,
+}
+
+struct Node {
+    data: NodeData,
+    parent: Option<Handle>,
+    children: Vec<Handle>,
+}
+
+/// A [`TreeSink`] that builds an arena of nodes that are then converted into
+/// [`Element`]s once parsing has finished.
+struct TreeBuilder {
+    nodes: RefCell<Vec<Node>>,
+}
+
+impl TreeBuilder {
+    fn new() -> Self {
+        Self {
+            nodes: RefCell::new(vec![Node {
+                data: NodeData::Document,
+                parent: None,
+                children: Vec::new(),
+            }]),
+        }
+    }
+
+    fn into_document(self) -> HtmlElement {
+        let nodes = self.nodes.into_inner();
+
+        nodes[DOCUMENT]
+            .children
+            .iter()
+            .find_map(|&child| to_element(&nodes, child).and_then(Element::html))
+            .unwrap_or_else(|| HtmlElement::new("html"))
+    }
+
+    fn into_fragment(self) -> Vec<Element> {
+        let nodes = self.nodes.into_inner();
+
+        // Fragments are parsed into a synthetic `<html>` element that is
+        // appended to the document.
+        let Some(&root) = nodes[DOCUMENT].children.first() else {
+            return Vec::new();
+        };
+
+        nodes[root]
+            .children
+            .iter()
+            .filter_map(|&child| to_element(&nodes, child))
+            .collect()
+    }
+
+    fn create_node(&self, data: NodeData) -> Handle {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(Node {
+            data,
+            parent: None,
+            children: Vec::new(),
+        });
+
+        nodes.len() - 1
+    }
+
+    fn detach(nodes: &mut [Node], target: Handle) {
+        if let Some(parent) = nodes[target].parent.take() {
+            nodes[parent].children.retain(|&child| child != target);
+        }
+    }
+
+    /// Inserts the given node or text into `parent`, at the given index.
+    ///
+    /// Text that is inserted next to an existing text node is merged into it.
+    fn insert(&self, parent: Handle, index: usize, child: NodeOrText<Handle>) {
+        let mut nodes = self.nodes.borrow_mut();
+
+        match child {
+            NodeOrText::AppendText(text) => {
+                if let Some(&previous) = index
+                    .checked_sub(1)
+                    .and_then(|index| nodes[parent].children.get(index))
+                {
+                    if let NodeData::Text(existing) = &mut nodes[previous].data {
+                        existing.push_tendril(&text);
+                        return;
+                    }
+                }
+
+                drop(nodes);
+                let node = self.create_node(NodeData::Text(text));
+                let mut nodes = self.nodes.borrow_mut();
+                nodes[node].parent = Some(parent);
+                nodes[parent].children.insert(index, node);
+            }
+            NodeOrText::AppendNode(node) => {
+                Self::detach(&mut nodes, node);
+                nodes[node].parent = Some(parent);
+
+                let index = index.min(nodes[parent].children.len());
+                nodes[parent].children.insert(index, node);
+            }
+        }
+    }
+}
+
+fn to_element(nodes: &[Node], handle: Handle) -> Option<Element> {
+    let node = &nodes[handle];
+
+    match &node.data {
+        NodeData::Element { name, attrs } => {
+            let mut element = HtmlElement::new(name.local.to_string());
+
+            for attr in attrs {
+                let name = match &attr.name.prefix {
+                    Some(prefix) => format!("{prefix}:{}", attr.name.local),
+                    None => attr.name.local.to_string(),
+                };
+
+                element.attrs.insert(name, attr.value.to_string());
+            }
+
+            element.children = node
+                .children
+                .iter()
+                .filter_map(|&child| to_element(nodes, child))
+                .collect();
+
+            Some(Element::Html(element))
+        }
+        NodeData::Text(text) => Some(Element::Text(TextElement::new(text.to_string()))),
+        NodeData::Comment(text) => Some(Element::Comment(CommentElement::new(text.to_string()))),
+        NodeData::Document | NodeData::Ignored => None,
+    }
+}
+
+impl TreeSink for TreeBuilder {
+    type Handle = Handle;
+    type Output = Self;
+    type ElemName<'a> = Ref<'a, QualName>;
+
+    fn finish(self) -> Self::Output {
+        self
+    }
+
+    fn parse_error(&self, _msg: Cow<'static, str>) {}
+
+    fn get_document(&self) -> Self::Handle {
+        DOCUMENT
+    }
+
+    fn elem_name<'a>(&'a self, target: &'a Self::Handle) -> Self::ElemName<'a> {
+        Ref::map(self.nodes.borrow(), |nodes| match &nodes[*target].data {
+            NodeData::Element { name, .. } => name,
+            _ => panic!("not an element"),
+        })
+    }
+
+    fn create_element(
+        &self,
+        name: QualName,
+        attrs: Vec<Attribute>,
+        _flags: ElementFlags,
+    ) -> Self::Handle {
+        self.create_node(NodeData::Element { name, attrs })
+    }
+
+    fn create_comment(&self, text: StrTendril) -> Self::Handle {
+        self.create_node(NodeData::Comment(text))
+    }
+
+    fn create_pi(&self, _target: StrTendril, _data: StrTendril) -> Self::Handle {
+        self.create_node(NodeData::Ignored)
+    }
+
+    fn append(&self, parent: &Self::Handle, child: NodeOrText<Self::Handle>) {
+        let index = self.nodes.borrow()[*parent].children.len();
+        self.insert(*parent, index, child);
+    }
+
+    fn append_based_on_parent_node(
+        &self,
+        element: &Self::Handle,
+        prev_element: &Self::Handle,
+        child: NodeOrText<Self::Handle>,
+    ) {
+        let has_parent = self.nodes.borrow()[*element].parent.is_some();
+        if has_parent {
+            self.append_before_sibling(element, child);
+        } else {
+            self.append(prev_element, child);
+        }
+    }
+
+    fn append_doctype_to_document(
+        &self,
+        _name: StrTendril,
+        _public_id: StrTendril,
+        _system_id: StrTendril,
+    ) {
+    }
+
+    fn get_template_contents(&self, target: &Self::Handle) -> Self::Handle {
+        *target
+    }
+
+    fn same_node(&self, x: &Self::Handle, y: &Self::Handle) -> bool {
+        x == y
+    }
+
+    fn set_quirks_mode(&self, _mode: QuirksMode) {}
+
+    fn append_before_sibling(&self, sibling: &Self::Handle, new_node: NodeOrText<Self::Handle>) {
+        let nodes = self.nodes.borrow();
+        let Some(parent) = nodes[*sibling].parent else {
+            return;
+        };
+        let index = nodes[parent]
+            .children
+            .iter()
+            .position(|child| child == sibling)
+            .unwrap_or(nodes[parent].children.len());
+        drop(nodes);
+
+        if let NodeOrText::AppendNode(node) = &new_node {
+            // Account for the node being moved from earlier within the same parent.
+            let nodes = self.nodes.borrow();
+            if nodes[*node].parent == Some(parent) {
+                let current = nodes[parent]
+                    .children
+                    .iter()
+                    .position(|child| child == node);
+                drop(nodes);
+                if current.is_some_and(|current| current < index) {
+                    self.insert(parent, index - 1, new_node);
+                    return;
+                }
+            }
+        }
+
+        self.insert(parent, index, new_node);
+    }
+
+    fn add_attrs_if_missing(&self, target: &Self::Handle, attrs: Vec<Attribute>) {
+        let mut nodes = self.nodes.borrow_mut();
+        let NodeData::Element {
+            attrs: existing, ..
+        } = &mut nodes[*target].data
+        else {
+            return;
+        };
+
+        for attr in attrs {
+            if !existing.iter().any(|existing| existing.name == attr.name) {
+                existing.push(attr);
+            }
+        }
+    }
+
+    fn remove_from_parent(&self, target: &Self::Handle) {
+        Self::detach(&mut self.nodes.borrow_mut(), *target);
+    }
+
+    fn reparent_children(&self, node: &Self::Handle, new_parent: &Self::Handle) {
+        let mut nodes = self.nodes.borrow_mut();
+        let children = std::mem::take(&mut nodes[*node].children);
+
+        for &child in &children {
+            nodes[child].parent = Some(*new_parent);
+        }
+
+        nodes[*new_parent].children.extend(children);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_yaml_snapshot;
+
+    use crate::renderer::HtmlElementRenderer;
+    use crate::Fragment;
+
+    use super::*;
+
+    fn render_fragment(elements: &[Element]) -> String {
+        HtmlElementRenderer::new()
+            .render_fragment_to_string(&Fragment::from(elements.to_vec()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_fragment() {
+        let elements = parse_fragment(r#"<div class="outer"><p>Hello, <em>world</em>!</p></div>"#);
+
+        assert_yaml_snapshot!(render_fragment(&elements));
+    }
+
+    #[test]
+    fn test_parse_fragment_void_elements() {
+        let elements = parse_fragment(r#"<p>Line one<br>Line two</p><img src="a.png" alt="A">"#);
+
+        assert_yaml_snapshot!(render_fragment(&elements));
+    }
+
+    #[test]
+    fn test_parse_fragment_implied_end_tags() {
+        let elements = parse_fragment("<ul><li>One<li>Two</ul><p>First<p>Second");
+
+        assert_yaml_snapshot!(render_fragment(&elements));
+    }
+
+    #[test]
+    fn test_parse_fragment_decodes_entities() {
+        let elements =
+            parse_fragment("<p title=\"&quot;Quoted&quot;\">Fish &amp; Chips&nbsp;&copy;</p>");
+
+        let p = elements[0].clone().html().unwrap();
+        assert_eq!(p.attrs.get("title"), Some(&"\"Quoted\"".to_string()));
+
+        let text = p.children[0].clone().text().unwrap();
+        assert_eq!(text.text, "Fish & Chips\u{a0}\u{a9}");
+        assert!(!text.safe);
+    }
+
+    #[test]
+    fn test_parse_document() {
+        let document =
+            parse_document("<!DOCTYPE html><title>Auk</title><body><h1>Hello from Auk!</h1>");
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .render_to_string(&document)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let html = r#"<!DOCTYPE html><html lang="en"><head><meta charset="utf-8"><title>Auk</title></head><body><!-- Content --><p class="lead">A &lt;b&gt; tag &amp; <a href="https://example.com">a link</a>.</p><hr></body></html>"#;
+
+        let rendered = HtmlElementRenderer::new()
+            .render_to_string(&parse_document(html))
+            .unwrap();
+
+        assert_eq!(rendered, html);
+    }
+}
@@ -1,91 +1,2108 @@
-//! Constructs for rendering [`HtmlElement`]s to HTML.
+//! Constructs for rendering [`HtmlElement`]s to HTML and plain text.
 
-use std::fmt::Write;
+mod text;
+
+use std::error::Error;
+use std::fmt::{self, Arguments};
 
 use pulldown_cmark_escape::{escape_href, escape_html, escape_html_body_text};
+pub use pulldown_cmark_escape::{FmtWriter, IoWriter, StrWrite};
+pub use text::TextRenderer;
 
+use crate::url::{DisallowedUrlAction, UrlPolicy, NEUTRALIZED_URL};
 use crate::visitor::Visitor;
-use crate::HtmlElement;
+use crate::{DoctypeElement, Element, Fragment, HtmlElement};
+
+/// An error that occurred while rendering.
+#[derive(Debug)]
+pub enum RenderError<E = fmt::Error> {
+    /// An error occurred while writing to the underlying writer.
+    Write(E),
+
+    /// An element has a tag name that is not valid HTML.
+    InvalidTagName(String),
+
+    /// An attribute has a name that is not valid HTML.
+    InvalidAttributeName(String),
+
+    /// A document type declaration has a name that is not valid.
+    InvalidDoctypeName(String),
+
+    /// A document type declaration has a public or system identifier that
+    /// cannot be quoted (i.e., it contains both `"` and `'`, or contains `>`).
+    InvalidDoctypeId(String),
+
+    /// A processing instruction has a target that is not valid.
+    InvalidProcessingInstructionTarget(String),
+
+    /// The text of a `<script>` data block (i.e., a `<script>` whose `type` is
+    /// neither JavaScript nor JSON) contains a sequence that would end the
+    /// element early, which cannot be escaped without knowing its format.
+    UnescapableScriptData {
+        /// The `type` of the `<script>`.
+        script_type: String,
+    },
+
+    /// An attribute contains a URL that is not allowed by the [`UrlPolicy`].
+    DisallowedUrl {
+        /// The name of the attribute.
+        attribute: String,
+
+        /// The value of the attribute.
+        url: String,
+    },
+}
+
+impl<E> From<E> for RenderError<E> {
+    fn from(value: E) -> Self {
+        Self::Write(value)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for RenderError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Write(err) => write!(f, "failed to write HTML: {err}"),
+            Self::InvalidTagName(name) => write!(f, "invalid tag name: {name:?}"),
+            Self::InvalidAttributeName(name) => write!(f, "invalid attribute name: {name:?}"),
+            Self::InvalidDoctypeName(name) => write!(f, "invalid doctype name: {name:?}"),
+            Self::InvalidDoctypeId(id) => write!(f, "invalid doctype identifier: {id:?}"),
+            Self::InvalidProcessingInstructionTarget(target) => {
+                write!(f, "invalid processing instruction target: {target:?}")
+            }
+            Self::UnescapableScriptData { script_type } => write!(
+                f,
+                "text of `<script type={script_type:?}>` would end the element early"
+            ),
+            Self::DisallowedUrl { attribute, url } => {
+                write!(f, "disallowed URL in `{attribute}` attribute: {url:?}")
+            }
+        }
+    }
+}
 
-/// A renderer for [`HtmlElement`]s to a string of HTML.
-pub struct HtmlElementRenderer {
-    html: String,
+impl<E: Error + 'static> Error for RenderError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Write(err) => Some(err),
+            Self::InvalidTagName(_)
+            | Self::InvalidAttributeName(_)
+            | Self::InvalidDoctypeName(_)
+            | Self::InvalidDoctypeId(_)
+            | Self::InvalidProcessingInstructionTarget(_)
+            | Self::UnescapableScriptData { .. }
+            | Self::DisallowedUrl { .. } => None,
+        }
+    }
+}
+
+/// Returns whether the given tag name is valid.
+///
+/// A valid tag name starts with an ASCII letter and does not contain any
+/// whitespace, control characters, or characters that would end the tag
+/// (e.g., `>` or `/`).
+pub fn is_valid_tag_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic())
+        && chars.all(|char| {
+            !char.is_whitespace()
+                && !char.is_control()
+                && !matches!(char, '/' | '>' | '<' | '"' | '\'' | '=')
+        })
+}
+
+/// Returns whether the given name matches the XML [`Name`](https://www.w3.org/TR/xml/#NT-Name)
+/// production, which is used for doctype names and processing instruction
+/// targets.
+fn is_valid_xml_name(name: &str) -> bool {
+    fn is_name_start_char(char: char) -> bool {
+        matches!(char,
+            ':' | 'A'..='Z' | '_' | 'a'..='z'
+            | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{2FF}'
+            | '\u{370}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}'
+            | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}'
+            | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}' | '\u{10000}'..='\u{EFFFF}')
+    }
+
+    fn is_name_char(char: char) -> bool {
+        is_name_start_char(char)
+            || matches!(char,
+                '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}' | '\u{203F}'..='\u{2040}')
+    }
+
+    let mut chars = name.chars();
+
+    chars.next().is_some_and(is_name_start_char) && chars.all(is_name_char)
+}
+
+/// Returns the quote to wrap the given doctype identifier in, or `None` if it
+/// cannot be quoted.
+fn doctype_id_quote(id: &str) -> Option<char> {
+    if id.contains('>') {
+        return None;
+    }
+
+    match (id.contains('"'), id.contains('\'')) {
+        (false, _) => Some('"'),
+        (true, false) => Some('\''),
+        (true, true) => None,
+    }
+}
+
+/// Returns whether the given attribute name is valid.
+///
+/// Follows the [HTML syntax for attribute names](https://html.spec.whatwg.org/multipage/syntax.html#attributes-2):
+/// one or more characters other than controls, spaces, `"`, `'`, `>`, `/`,
+/// `=`, and noncharacters.
+pub fn is_valid_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|char| {
+            !char.is_whitespace()
+                && !char.is_control()
+                && !matches!(char, '"' | '\'' | '>' | '/' | '=' | '<')
+                && !is_noncharacter(char)
+        })
+}
+
+fn is_noncharacter(char: char) -> bool {
+    matches!(char, '\u{FDD0}'..='\u{FDEF}') || (char as u32 & 0xFFFE) == 0xFFFE
+}
+
+/// The mode an [`HtmlElementRenderer`] renders HTML in.
+#[derive(Debug, Clone, Default)]
+pub enum RenderMode {
+    /// Renders the HTML on a single line, without any insignificant whitespace.
+    #[default]
+    Compact,
+
+    /// Renders the HTML with line breaks and indentation.
+    Pretty(PrettyOptions),
+
+    /// Renders the HTML as small as possible, while still parsing back to an
+    /// equivalent tree.
+    ///
+    /// In this mode:
+    ///
+    /// - [Optional end tags](https://html.spec.whatwg.org/multipage/syntax.html#optional-tags)
+    ///   are omitted (e.g., `</li>` or `</p>`).
+    /// - Attribute values are only quoted when required.
+    /// - Boolean attributes are shortened (e.g., `disabled="disabled"` becomes `disabled`).
+    /// - Runs of whitespace in text are collapsed into a single space, and
+    ///   whitespace between block-level elements is removed.
+    ///
+    /// The contents of `<pre>`, `<textarea>`, and raw text elements (e.g.,
+    /// `<script>` and `<style>`) are left untouched.
+    Minified,
+}
+
+/// The syntax an [`HtmlElementRenderer`] renders elements in.
+#[derive(Debug, Clone, Default)]
+pub enum Syntax {
+    /// Renders elements as HTML.
+    #[default]
+    Html,
+
+    /// Renders elements as XML (e.g., for RSS feeds, sitemaps, or SVG files).
+    ///
+    /// Compared to HTML:
+    ///
+    /// - Elements without children are self-closing (e.g., `<link/>`).
+    /// - Text and attribute values are escaped according to the rules of XML,
+    ///   including inside of elements like `<script>` and `<style>`.
+    /// - Attribute values are always quoted, even when empty.
+    /// - No document type declaration is inserted for `<html>` elements.
+    /// - [`RenderMode::Minified`] renders the same as [`RenderMode::Compact`],
+    ///   as omitting end tags or quotes is not valid XML.
+    ///
+    /// Tag and attribute names are always rendered exactly as given, so
+    /// namespace prefixes (e.g., `atom:link` or `xlink:href`) are preserved.
+    Xml(XmlOptions),
+}
+
+/// The options for rendering in [`Syntax::Xml`].
+#[derive(Debug, Clone)]
+pub struct XmlOptions {
+    /// Whether to render the XML declaration (`<?xml version="1.0" encoding="UTF-8"?>`)
+    /// before the rendered elements.
+    pub declaration: bool,
+}
+
+impl Default for XmlOptions {
+    fn default() -> Self {
+        Self { declaration: true }
+    }
+}
+
+/// The options for rendering HTML in [`RenderMode::Pretty`].
+#[derive(Debug, Clone)]
+pub struct PrettyOptions {
+    /// The number of spaces to indent each level of nesting by.
+    pub indent: usize,
+
+    /// The maximum width of a line.
+    ///
+    /// Elements that fit within this width will be kept on a single line.
+    pub max_width: usize,
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            max_width: 80,
+        }
+    }
+}
+
+/// A renderer for [`HtmlElement`]s to HTML.
+///
+/// By default the HTML is rendered into a [`String`], but any [`StrWrite`]
+/// sink can be rendered into using [`HtmlElementRenderer::with_writer`]:
+///
+/// ```
+/// use auk::*;
+/// use auk::renderer::{HtmlElementRenderer, IoWriter};
+///
+/// let mut stdout = std::io::stdout();
+///
+/// HtmlElementRenderer::with_writer(IoWriter(&mut stdout))
+///     .render(&p().child("Hello from Auk!"))
+///     .unwrap();
+/// ```
+#[derive(Default)]
+pub struct HtmlElementRenderer<W: StrWrite = String> {
+    writer: W,
+    mode: RenderMode,
+    syntax: Syntax,
+    url_policy: Option<UrlPolicy>,
+    depth: usize,
+    /// The tag name of the raw text element whose text is being rendered.
+    raw_text_parent: Option<RawText>,
+    /// Whether the whitespace of the text being rendered is significant.
+    preserve_whitespace: bool,
+    /// Whether the element being rendered is inside of foreign content (i.e., SVG or MathML).
+    in_foreign_content: bool,
+    /// Whether the end tag of the element being rendered should be omitted.
+    omit_end_tag: bool,
+    /// Whether a document type declaration has been rendered.
+    wrote_doctype: bool,
 }
 
 impl HtmlElementRenderer {
     /// Returns a new [`HtmlElementRenderer`].
     pub fn new() -> Self {
-        Self {
-            html: String::new(),
-        }
+        Self::with_writer(String::new())
     }
 
     /// Returns the rendered HTML.
     pub fn html(&self) -> &str {
-        &self.html
+        &self.writer
     }
 
     /// Renders the given [`HtmlElement`] to a string of HTML.
-    pub fn render_to_string(mut self, element: &HtmlElement) -> Result<String, std::fmt::Error> {
+    pub fn render_to_string(self, element: &HtmlElement) -> Result<String, RenderError> {
+        self.render(element)
+    }
+
+    /// Renders the given [`Fragment`] to a string of HTML.
+    pub fn render_fragment_to_string(self, fragment: &Fragment) -> Result<String, RenderError> {
+        self.render_fragment(fragment)
+    }
+}
+
+impl<W: StrWrite> HtmlElementRenderer<W> {
+    /// Returns a new [`HtmlElementRenderer`] that renders into the given writer.
+    ///
+    /// Use [`IoWriter`] to render into a [`std::io::Write`] and [`FmtWriter`]
+    /// to render into a [`std::fmt::Write`].
+    pub fn with_writer(writer: W) -> Self {
+        Self {
+            writer,
+            mode: RenderMode::Compact,
+            syntax: Syntax::Html,
+            url_policy: None,
+            depth: 0,
+            raw_text_parent: None,
+            preserve_whitespace: false,
+            in_foreign_content: false,
+            omit_end_tag: false,
+            wrote_doctype: false,
+        }
+    }
+
+    /// Sets the [`RenderMode`] for this renderer.
+    pub fn mode(mut self, mode: RenderMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets this renderer to render in [`RenderMode::Pretty`] with the default [`PrettyOptions`].
+    pub fn pretty(self) -> Self {
+        self.mode(RenderMode::Pretty(PrettyOptions::default()))
+    }
+
+    /// Sets this renderer to render in [`RenderMode::Minified`].
+    pub fn minified(self) -> Self {
+        self.mode(RenderMode::Minified)
+    }
+
+    /// Sets the [`Syntax`] for this renderer.
+    pub fn syntax(mut self, syntax: Syntax) -> Self {
+        self.syntax = syntax;
+        self
+    }
+
+    /// Sets this renderer to render in [`Syntax::Xml`] with the default [`XmlOptions`].
+    pub fn xml(self) -> Self {
+        self.syntax(Syntax::Xml(XmlOptions::default()))
+    }
+
+    /// Sets the [`UrlPolicy`] for this renderer.
+    ///
+    /// URLs in URL-bearing attributes that are not allowed by the policy will
+    /// either be neutralized or rejected, depending on the policy.
+    ///
+    /// By default no [`UrlPolicy`] is applied.
+    pub fn url_policy(mut self, url_policy: impl Into<Option<UrlPolicy>>) -> Self {
+        self.url_policy = url_policy.into();
+        self
+    }
+
+    /// Renders the given [`HtmlElement`] into the writer and returns the writer.
+    pub fn render(mut self, element: &HtmlElement) -> Result<W, RenderError<W::Error>> {
+        self.write_xml_declaration()?;
+
+        if self.is_minified() {
+            self.omit_end_tag = can_omit_end_tag(element, None, None);
+        }
+
         self.visit(element)?;
 
-        Ok(self.html)
+        Ok(self.into_inner())
+    }
+
+    /// Renders the given [`Fragment`] into the writer and returns the writer.
+    pub fn render_fragment(mut self, fragment: &Fragment) -> Result<W, RenderError<W::Error>> {
+        self.write_xml_declaration()?;
+        self.visit_fragment(fragment)?;
+
+        Ok(self.into_inner())
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn is_xml(&self) -> bool {
+        matches!(self.syntax, Syntax::Xml(_))
+    }
+
+    /// Returns whether HTML is being rendered in [`RenderMode::Minified`].
+    fn is_minified(&self) -> bool {
+        matches!(self.mode, RenderMode::Minified) && !self.is_xml()
+    }
+
+    fn write_xml_declaration(&mut self) -> Result<(), W::Error> {
+        if let Syntax::Xml(XmlOptions { declaration: true }) = self.syntax {
+            write!(self.writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+            self.write_line_break()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether whitespace may be inserted between the given children
+    /// without changing how they are displayed.
+    fn can_break_between(&self, children: &[Element]) -> bool {
+        if self.is_xml() {
+            children
+                .iter()
+                .all(|child| !matches!(child, Element::Text(_) | Element::CData(_)))
+        } else {
+            are_blocks(children)
+        }
+    }
+
+    /// Returns whether the children of the given element should each be
+    /// rendered on their own line.
+    ///
+    /// Whitespace is only ever inserted between block-level elements, as
+    /// inserting it between inline content or inside of preformatted elements
+    /// would change how the document is displayed.
+    fn should_break(&self, element: &HtmlElement) -> bool {
+        let RenderMode::Pretty(options) = &self.mode else {
+            return false;
+        };
+
+        if element.children.is_empty()
+            || !self.is_xml() && is_preformatted(&element.tag_name)
+            || !self.can_break_between(&element.children)
+        {
+            return false;
+        }
+
+        let syntax = match self.syntax {
+            Syntax::Html => Syntax::Html,
+            Syntax::Xml(_) => Syntax::Xml(XmlOptions { declaration: false }),
+        };
+
+        let Some(max_width) = options.max_width.checked_sub(self.depth * options.indent) else {
+            return true;
+        };
+
+        // Counting stops as soon as the element no longer fits, so that
+        // measuring each level of nesting does not re-render the whole subtree.
+        let result = HtmlElementRenderer::with_writer(WidthCounter::new(max_width))
+            .syntax(syntax)
+            .render(element);
+
+        matches!(result, Err(RenderError::Write(WidthExceeded)))
+    }
+
+    /// Visits the given children in [`RenderMode::Minified`].
+    ///
+    /// Insignificant whitespace is dropped, and the end tags of elements are
+    /// omitted wherever the element that follows them allows it.
+    fn visit_minified_children(
+        &mut self,
+        parent: Option<&HtmlElement>,
+        children: &[Element],
+    ) -> Result<(), RenderError<W::Error>> {
+        let children = children
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| {
+                self.preserve_whitespace || !is_insignificant_whitespace(parent, children, index)
+            })
+            .map(|(_, child)| child)
+            .collect::<Vec<_>>();
+
+        for (index, child) in children.iter().enumerate() {
+            if let Element::Html(element) = child {
+                self.omit_end_tag =
+                    can_omit_end_tag(element, children.get(index + 1).copied(), parent);
+            }
+
+            self.visit_children(std::slice::from_ref(*child))?;
+        }
+
+        Ok(())
+    }
+
+    fn write_line_break(&mut self) -> Result<(), W::Error> {
+        if let RenderMode::Pretty(options) = &self.mode {
+            write!(
+                self.writer,
+                "\n{:indent$}",
+                "",
+                indent = self.depth * options.indent
+            )?;
+        }
+
+        Ok(())
     }
 }
 
-impl Visitor for HtmlElementRenderer {
-    type Error = std::fmt::Error;
+impl<W: StrWrite> Visitor for HtmlElementRenderer<W> {
+    type Error = RenderError<W::Error>;
 
     fn visit(&mut self, element: &HtmlElement) -> Result<(), Self::Error> {
-        if element.tag_name == "html" {
-            write!(&mut self.html, "<!DOCTYPE html>")?;
+        if !is_valid_tag_name(&element.tag_name) {
+            return Err(RenderError::InvalidTagName(element.tag_name.clone()));
         }
 
-        write!(&mut self.html, "<{}", element.tag_name)?;
+        let omit_end_tag = std::mem::take(&mut self.omit_end_tag);
+        let xml = self.is_xml();
+        let foreign =
+            self.in_foreign_content || matches!(element.tag_name.as_str(), "svg" | "math");
+
+        if !xml && element.tag_name == "html" && !self.wrote_doctype {
+            self.visit_doctype(&DoctypeElement::html())?;
+            self.write_line_break()?;
+        }
+
+        write!(self.writer, "<{}", element.tag_name)?;
 
         for (name, value) in &element.attrs {
             self.visit_attr(name, value)?;
         }
 
-        write!(&mut self.html, ">")?;
+        // Unlike HTML elements, foreign elements may be self-closing.
+        if (xml || foreign) && element.children.is_empty() {
+            // An unquoted attribute value would take the `/` as part of the value.
+            if self.is_minified() && !element.attrs.is_empty() {
+                write!(self.writer, " ")?;
+            }
+
+            write!(self.writer, "/>")?;
+            return Ok(());
+        }
+
+        write!(self.writer, ">")?;
 
-        if element.is_void() {
+        if !xml && !foreign && element.is_void() {
             return Ok(());
         }
 
-        self.visit_children(&element.children)?;
+        // The parser drops a newline immediately following the start tag of
+        // these elements, so we need to add another one to preserve it.
+        if !xml
+            && !foreign
+            && matches!(element.tag_name.as_str(), "pre" | "textarea" | "listing")
+            && element.children.first().is_some_and(
+                |child| matches!(child, Element::Text(text) if text.text.starts_with('\n')),
+            )
+        {
+            self.writer.write_str("\n")?;
+        }
+
+        let raw_text_parent = std::mem::replace(
+            &mut self.raw_text_parent,
+            (!xml && !foreign && element.is_raw_text()).then(|| RawText::new(element)),
+        );
 
-        write!(&mut self.html, "</{}>", element.tag_name)?;
+        let in_foreign_content = std::mem::replace(
+            &mut self.in_foreign_content,
+            foreign && !is_html_integration_point(&element.tag_name),
+        );
+
+        let preserve_whitespace = self.preserve_whitespace;
+        self.preserve_whitespace |= is_preformatted(&element.tag_name);
+
+        if self.is_minified() {
+            self.visit_minified_children(Some(element), &element.children)?;
+        } else if self.should_break(element) {
+            self.depth += 1;
+            for child in &element.children {
+                self.write_line_break()?;
+                self.visit_children(std::slice::from_ref(child))?;
+            }
+            self.depth -= 1;
+            self.write_line_break()?;
+        } else {
+            let mode = std::mem::take(&mut self.mode);
+            self.visit_children(&element.children)?;
+            self.mode = mode;
+        }
+
+        self.raw_text_parent = raw_text_parent;
+        self.preserve_whitespace = preserve_whitespace;
+        self.in_foreign_content = in_foreign_content;
+
+        if !omit_end_tag {
+            write!(self.writer, "</{}>", element.tag_name)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_fragment(&mut self, fragment: &Fragment) -> Result<(), Self::Error> {
+        if self.is_minified() {
+            return self.visit_minified_children(None, &fragment.children);
+        }
+
+        let break_children = matches!(self.mode, RenderMode::Pretty(_))
+            && self.can_break_between(&fragment.children);
+
+        for (index, child) in fragment.children.iter().enumerate() {
+            if break_children && index > 0 {
+                self.write_line_break()?;
+            }
+
+            self.visit_children(std::slice::from_ref(child))?;
+        }
 
         Ok(())
     }
 
     fn visit_text(&mut self, text: &str, safe: bool) -> Result<(), Self::Error> {
         if safe {
-            write!(&mut self.html, "{text}")?;
+            self.writer.write_str(text)?;
+        } else if self.is_xml() {
+            escape_xml(&mut self.writer, text)?;
+        } else if let Some(raw_text) = &self.raw_text_parent {
+            escape_raw_text(&mut self.writer, text, raw_text)?;
+        } else if self.is_minified() && !self.preserve_whitespace {
+            escape_html_body_text(&mut self.writer, &collapse_whitespace(text))?;
         } else {
-            escape_html_body_text(&mut self.html, text)?;
+            escape_html_body_text(&mut self.writer, text)?;
         }
 
         Ok(())
     }
 
     fn visit_attr(&mut self, name: &str, value: &str) -> Result<(), Self::Error> {
-        write!(&mut self.html, " ")?;
-        write!(&mut self.html, "{name}")?;
+        if !is_valid_attribute_name(name) {
+            return Err(RenderError::InvalidAttributeName(name.to_string()));
+        }
 
-        if !value.is_empty() {
-            write!(&mut self.html, "=")?;
-            write!(&mut self.html, "\"")?;
-            if name == "href" || name == "src" {
-                escape_href(&mut self.html, value)?;
+        let value = match &self.url_policy {
+            Some(url_policy) if !url_policy.is_attribute_allowed(name, value) => {
+                match url_policy.disallowed_action() {
+                    DisallowedUrlAction::Neutralize => NEUTRALIZED_URL,
+                    DisallowedUrlAction::Reject => {
+                        return Err(RenderError::DisallowedUrl {
+                            attribute: name.to_string(),
+                            url: value.to_string(),
+                        });
+                    }
+                }
+            }
+            _ => value,
+        };
+
+        write!(self.writer, " ")?;
+        write!(self.writer, "{name}")?;
+
+        if self.is_xml() {
+            write!(self.writer, "=\"")?;
+            escape_xml_attribute(&mut self.writer, value)?;
+            write!(self.writer, "\"")?;
+
+            return Ok(());
+        }
+
+        if self.is_minified() {
+            if value.is_empty() || is_boolean_attribute(name) && value.eq_ignore_ascii_case(name) {
+                return Ok(());
+            }
+
+            let mut escaped = String::new();
+            // Writing to a `String` cannot fail.
+            let _ = escape_attribute_value(&mut escaped, name, value);
+
+            if needs_quotes(&escaped) {
+                write!(self.writer, "=\"{escaped}\"")?;
             } else {
-                escape_html(&mut self.html, value)?;
+                write!(self.writer, "={escaped}")?;
             }
 
-            write!(&mut self.html, "\"")?;
+            return Ok(());
+        }
+
+        if !value.is_empty() {
+            write!(self.writer, "=")?;
+            write!(self.writer, "\"")?;
+            escape_attribute_value(&mut self.writer, name, value)?;
+            write!(self.writer, "\"")?;
         }
 
         Ok(())
     }
+
+    fn visit_comment(&mut self, text: &str) -> Result<(), Self::Error> {
+        write!(self.writer, "<!--")?;
+        if self.is_xml() {
+            escape_xml_comment(&mut self.writer, text)?;
+        } else {
+            escape_comment(&mut self.writer, text)?;
+        }
+        write!(self.writer, "-->")?;
+
+        Ok(())
+    }
+
+    fn visit_doctype(&mut self, doctype: &DoctypeElement) -> Result<(), Self::Error> {
+        if !is_valid_xml_name(&doctype.name) {
+            return Err(RenderError::InvalidDoctypeName(doctype.name.clone()));
+        }
+
+        let quote = |id: &String| {
+            doctype_id_quote(id)
+                .map(|quote| format!("{quote}{id}{quote}"))
+                .ok_or_else(|| RenderError::InvalidDoctypeId(id.clone()))
+        };
+        let public_id = doctype.public_id.as_ref().map(quote).transpose()?;
+        let system_id = doctype.system_id.as_ref().map(quote).transpose()?;
+
+        write!(self.writer, "<!DOCTYPE {}", doctype.name)?;
+
+        match (public_id, system_id) {
+            (Some(public_id), Some(system_id)) => {
+                write!(self.writer, " PUBLIC {public_id} {system_id}")?
+            }
+            (Some(public_id), None) => write!(self.writer, " PUBLIC {public_id}")?,
+            (None, Some(system_id)) => write!(self.writer, " SYSTEM {system_id}")?,
+            (None, None) => {}
+        }
+
+        write!(self.writer, ">")?;
+
+        self.wrote_doctype = true;
+
+        Ok(())
+    }
+
+    fn visit_processing_instruction(
+        &mut self,
+        target: &str,
+        data: &str,
+    ) -> Result<(), Self::Error> {
+        if !is_valid_xml_name(target) {
+            return Err(RenderError::InvalidProcessingInstructionTarget(
+                target.to_string(),
+            ));
+        }
+
+        if self.is_xml() {
+            write!(self.writer, "<?{target}")?;
+            if !data.is_empty() {
+                write!(self.writer, " {}", data.replace("?>", "? >"))?;
+            }
+            write!(self.writer, "?>")?;
+
+            return Ok(());
+        }
+
+        // HTML parses processing instructions as bogus comments, which end at the first `>`.
+        write!(self.writer, "<?{target} {}>", data.replace('>', "&gt;"))?;
+
+        Ok(())
+    }
+
+    fn visit_cdata(&mut self, text: &str) -> Result<(), Self::Error> {
+        // HTML only recognizes CDATA sections inside of foreign content. Anywhere else they are
+        // parsed as bogus comments, which end at the first `>`, so write the text as text instead.
+        if !self.is_xml() && !self.in_foreign_content {
+            return self.visit_text(text, false);
+        }
+
+        write!(
+            self.writer,
+            "<![CDATA[{}]]>",
+            text.replace("]]>", "]]]]><![CDATA[>")
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Writes the escaped value of the attribute with the given name.
+fn escape_attribute_value<W: StrWrite>(w: W, name: &str, value: &str) -> Result<(), W::Error> {
+    if name == "href" || name == "src" {
+        escape_href(w, value)
+    } else {
+        escape_html(w, value)
+    }
+}
+
+/// Returns whether the given (escaped) attribute value needs to be quoted.
+///
+/// See the [HTML syntax for unquoted attribute values](https://html.spec.whatwg.org/multipage/syntax.html#unquoted).
+fn needs_quotes(value: &str) -> bool {
+    value.is_empty()
+        || value.contains(|char: char| {
+            char.is_ascii_whitespace() || matches!(char, '"' | '\'' | '=' | '<' | '>' | '`')
+        })
+}
+
+/// Returns whether the attribute with the given name is a [boolean attribute](https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#boolean-attributes).
+fn is_boolean_attribute(name: &str) -> bool {
+    matches!(
+        name,
+        "allowfullscreen"
+            | "async"
+            | "autofocus"
+            | "autoplay"
+            | "checked"
+            | "controls"
+            | "default"
+            | "defer"
+            | "disabled"
+            | "formnovalidate"
+            | "hidden"
+            | "inert"
+            | "ismap"
+            | "itemscope"
+            | "loop"
+            | "multiple"
+            | "muted"
+            | "nomodule"
+            | "novalidate"
+            | "open"
+            | "playsinline"
+            | "readonly"
+            | "required"
+            | "reversed"
+            | "selected"
+    )
+}
+
+/// Collapses each run of ASCII whitespace in the given text into a single space.
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut in_whitespace = false;
+
+    for char in text.chars() {
+        if char.is_ascii_whitespace() {
+            if !in_whitespace {
+                collapsed.push(' ');
+            }
+        } else {
+            collapsed.push(char);
+        }
+
+        in_whitespace = char.is_ascii_whitespace();
+    }
+
+    collapsed
+}
+
+/// Returns whether the child at the given index is whitespace that does not
+/// affect how the document is displayed.
+///
+/// This is whitespace-only text whose neighbors are both block-level elements
+/// (or the start or end of a block-level parent).
+fn is_insignificant_whitespace(
+    parent: Option<&HtmlElement>,
+    children: &[Element],
+    index: usize,
+) -> bool {
+    let Element::Text(text) = &children[index] else {
+        return false;
+    };
+
+    if text.safe || !text.text.chars().all(|char| char.is_ascii_whitespace()) {
+        return false;
+    }
+
+    let is_block_boundary = |sibling: Option<&Element>| match sibling {
+        Some(sibling) => are_blocks(std::slice::from_ref(sibling)),
+        None => parent.is_some_and(|parent| is_block(&parent.tag_name)),
+    };
+
+    is_block_boundary(index.checked_sub(1).map(|index| &children[index]))
+        && is_block_boundary(children.get(index + 1))
+}
+
+/// Returns whether the end tag of the given element may be omitted, based on
+/// the node that follows it and its parent.
+///
+/// Follows the rules for [optional tags](https://html.spec.whatwg.org/multipage/syntax.html#optional-tags).
+/// When the element has no parent (i.e., it is at the root of what is being
+/// rendered) we cannot know what follows it, so only the end tag of `<html>`
+/// may be omitted.
+fn can_omit_end_tag(
+    element: &HtmlElement,
+    next: Option<&Element>,
+    parent: Option<&HtmlElement>,
+) -> bool {
+    let followed_by = |tag_names: &[&str]| matches!(next, Some(Element::Html(next)) if tag_names.contains(&next.tag_name.as_str()));
+    let is_last = next.is_none() && parent.is_some();
+
+    match element.tag_name.as_str() {
+        "html" => !matches!(next, Some(Element::Comment(_))),
+        "body" => parent.is_some() && !matches!(next, Some(Element::Comment(_))),
+        "head" | "colgroup" | "caption" => {
+            parent.is_some() && !matches!(next, Some(Element::Comment(_) | Element::Text(_)))
+        }
+        "li" => followed_by(&["li"]) || is_last,
+        "dt" => followed_by(&["dt", "dd"]),
+        "dd" => followed_by(&["dd", "dt"]) || is_last,
+        "p" => {
+            followed_by(&[
+                "address",
+                "article",
+                "aside",
+                "blockquote",
+                "details",
+                "dialog",
+                "div",
+                "dl",
+                "fieldset",
+                "figcaption",
+                "figure",
+                "footer",
+                "form",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "header",
+                "hgroup",
+                "hr",
+                "main",
+                "menu",
+                "nav",
+                "ol",
+                "p",
+                "pre",
+                "search",
+                "section",
+                "table",
+                "ul",
+            ]) || is_last
+                && parent.is_some_and(|parent| {
+                    !matches!(
+                        parent.tag_name.as_str(),
+                        "a" | "audio" | "del" | "ins" | "map" | "noscript" | "video"
+                    ) && !parent.tag_name.contains('-')
+                })
+        }
+        "rt" | "rp" => followed_by(&["rt", "rp"]) || is_last,
+        "optgroup" => followed_by(&["optgroup", "hr"]) || is_last,
+        "option" => followed_by(&["option", "optgroup", "hr"]) || is_last,
+        "thead" => followed_by(&["tbody", "tfoot"]),
+        "tbody" => followed_by(&["tbody", "tfoot"]) || is_last,
+        "tfoot" => is_last,
+        "tr" => followed_by(&["tr"]) || is_last,
+        "td" | "th" => followed_by(&["td", "th"]) || is_last,
+        _ => false,
+    }
+}
+
+/// The kind of raw text element whose text is being rendered, which
+/// determines how sequences that would end the element early are escaped.
+#[derive(Debug, Clone)]
+enum RawText {
+    /// A `<script>` containing JavaScript.
+    JavaScript,
+
+    /// A `<script>` containing JSON (e.g., `type="application/json"` or
+    /// `type="importmap"`).
+    Json,
+
+    /// A `<script>` data block in any other format.
+    DataBlock(String),
+
+    /// Any other raw text element (e.g., `<style>`).
+    Other(String),
+}
+
+impl RawText {
+    fn new(element: &HtmlElement) -> Self {
+        if element.tag_name != "script" {
+            return Self::Other(element.tag_name.clone());
+        }
+
+        let script_type = element.attrs.get("type").map_or("", |type_| type_.trim());
+        let essence = script_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        match essence.as_str() {
+            ""
+            | "module"
+            | "application/ecmascript"
+            | "application/javascript"
+            | "application/x-ecmascript"
+            | "application/x-javascript"
+            | "text/ecmascript"
+            | "text/javascript"
+            | "text/javascript1.0"
+            | "text/javascript1.1"
+            | "text/javascript1.2"
+            | "text/javascript1.3"
+            | "text/javascript1.4"
+            | "text/javascript1.5"
+            | "text/jscript"
+            | "text/livescript"
+            | "text/x-ecmascript"
+            | "text/x-javascript" => Self::JavaScript,
+            "importmap" | "speculationrules" => Self::Json,
+            essence if essence.ends_with("/json") || essence.ends_with("+json") => Self::Json,
+            _ => Self::DataBlock(script_type.to_string()),
+        }
+    }
+}
+
+/// Writes the text of a raw text element.
+///
+/// The text is written as-is, as raw text elements do not support character
+/// references. The only exception are sequences that would end the element
+/// early (e.g., `</script`), and inside of a `<script>` the `<!--` sequence, as
+/// it causes the parser to look for a different end to the element.
+///
+/// How these are neutralized depends on the contents of the element:
+///
+/// - In JavaScript and CSS the `/` (or `!`) is escaped with a backslash.
+/// - In JSON the `<` is written as `\u003c`, which is only valid inside of
+///   strings, the only place the sequences can appear in valid JSON.
+/// - In any other data block they cannot be escaped without knowing the
+///   format, so they are rejected with [`RenderError::UnescapableScriptData`].
+fn escape_raw_text<W: StrWrite>(
+    mut w: W,
+    text: &str,
+    raw_text: &RawText,
+) -> Result<(), RenderError<W::Error>> {
+    let tag_name = match raw_text {
+        RawText::JavaScript | RawText::Json | RawText::DataBlock(_) => "script",
+        RawText::Other(tag_name) => tag_name,
+    };
+
+    let mut written = 0;
+
+    for (index, _) in text.match_indices('<') {
+        let rest = &text[index + 1..];
+
+        let is_end_tag = rest.strip_prefix('/').is_some_and(|rest| {
+            rest.get(..tag_name.len())
+                .is_some_and(|name| name.eq_ignore_ascii_case(tag_name))
+        });
+        let is_comment_start = tag_name == "script" && rest.starts_with("!--");
+
+        if !is_end_tag && !is_comment_start {
+            continue;
+        }
+
+        match raw_text {
+            RawText::JavaScript | RawText::Other(_) => {
+                w.write_str(&text[written..=index])?;
+                w.write_str("\\")?;
+                written = index + 1;
+            }
+            RawText::Json => {
+                w.write_str(&text[written..index])?;
+                w.write_str("\\u003c")?;
+                written = index + 1;
+            }
+            RawText::DataBlock(script_type) => {
+                return Err(RenderError::UnescapableScriptData {
+                    script_type: script_type.clone(),
+                });
+            }
+        }
+    }
+
+    w.write_str(&text[written..])?;
+
+    Ok(())
+}
+
+/// Writes the text of a comment.
+///
+/// The text of a comment [must not](https://html.spec.whatwg.org/multipage/syntax.html#comments)
+/// contain sequences that would end the comment early (e.g., `-->`). These are
+/// neutralized by replacing the offending `<` or `>` with a character reference.
+///
+/// Character references are not decoded inside of comments, so this is purely
+/// to ensure the comment is well-formed.
+fn escape_comment<W: StrWrite>(mut w: W, text: &str) -> Result<(), W::Error> {
+    let mut text = text
+        .replace("<!--", "&lt;!--")
+        .replace("--!>", "--!&gt;")
+        .replace("-->", "--&gt;");
+
+    if let Some(rest) = text.strip_prefix('>') {
+        text = format!("&gt;{rest}");
+    } else if let Some(rest) = text.strip_prefix("->") {
+        text = format!("-&gt;{rest}");
+    }
+
+    if let Some(rest) = text.strip_suffix("<!-") {
+        text = format!("{rest}&lt;!-");
+    }
+
+    w.write_str(&text)
+}
+
+/// Writes the given text, escaped according to the rules of XML.
+fn escape_xml<W: StrWrite>(w: W, text: &str) -> Result<(), W::Error> {
+    escape_xml_with(w, text, |char| match char {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        _ => None,
+    })
+}
+
+/// Writes the given attribute value, escaped according to the rules of XML.
+///
+/// Whitespace other than spaces is escaped as well, as XML parsers would
+/// otherwise [normalize](https://www.w3.org/TR/xml/#AVNormalize) it into spaces.
+fn escape_xml_attribute<W: StrWrite>(w: W, value: &str) -> Result<(), W::Error> {
+    escape_xml_with(w, value, |char| match char {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        '"' => Some("&quot;"),
+        '\t' => Some("&#9;"),
+        '\n' => Some("&#10;"),
+        '\r' => Some("&#13;"),
+        _ => None,
+    })
+}
+
+fn escape_xml_with<W: StrWrite>(
+    mut w: W,
+    text: &str,
+    escape: impl Fn(char) -> Option<&'static str>,
+) -> Result<(), W::Error> {
+    let mut written = 0;
+
+    for (index, char) in text.char_indices() {
+        if let Some(escaped) = escape(char) {
+            w.write_str(&text[written..index])?;
+            w.write_str(escaped)?;
+            written = index + char.len_utf8();
+        }
+    }
+
+    w.write_str(&text[written..])
+}
+
+/// Writes the text of an XML comment.
+///
+/// XML comments [must not](https://www.w3.org/TR/xml/#sec-comments) contain
+/// `--` or end with `-`, so these are broken up with spaces.
+fn escape_xml_comment<W: StrWrite>(mut w: W, text: &str) -> Result<(), W::Error> {
+    let mut text = text.to_string();
+    while text.contains("--") {
+        text = text.replace("--", "- -");
+    }
+
+    if text.ends_with('-') {
+        text.push(' ');
+    }
+
+    w.write_str(&text)
+}
+
+/// A [`StrWrite`] that only counts the width of what is written to it, and
+/// fails once the width exceeds the maximum.
+struct WidthCounter {
+    width: usize,
+    max_width: usize,
+}
+
+impl WidthCounter {
+    fn new(max_width: usize) -> Self {
+        Self {
+            width: 0,
+            max_width,
+        }
+    }
+}
+
+/// The error returned by a [`WidthCounter`] once its maximum width is exceeded.
+#[derive(Debug)]
+struct WidthExceeded;
+
+impl std::fmt::Write for WidthCounter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        StrWrite::write_str(self, s).map_err(|_| std::fmt::Error)
+    }
+}
+
+impl StrWrite for WidthCounter {
+    type Error = WidthExceeded;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.width += s.len();
+        if self.width > self.max_width {
+            return Err(WidthExceeded);
+        }
+
+        Ok(())
+    }
+
+    fn write_fmt(&mut self, args: Arguments) -> Result<(), Self::Error> {
+        std::fmt::Write::write_fmt(self, args).map_err(|_| WidthExceeded)
+    }
+}
+
+/// Returns whether all of the given elements are rendered as blocks, such that
+/// whitespace may be inserted between them.
+fn are_blocks(elements: &[Element]) -> bool {
+    elements.iter().all(|element| match element {
+        Element::Html(element) => is_block(&element.tag_name),
+        Element::Comment(_) | Element::Doctype(_) | Element::ProcessingInstruction(_) => true,
+        Element::Text(_) | Element::CData(_) => false,
+    })
+}
+
+/// Returns whether the element with the given tag name is rendered as a block,
+/// such that whitespace surrounding it is insignificant.
+fn is_block(tag_name: &str) -> bool {
+    matches!(
+        tag_name,
+        "address"
+            | "article"
+            | "aside"
+            | "base"
+            | "blockquote"
+            | "body"
+            | "caption"
+            | "col"
+            | "colgroup"
+            | "dd"
+            | "details"
+            | "dialog"
+            | "div"
+            | "dl"
+            | "dt"
+            | "fieldset"
+            | "figcaption"
+            | "figure"
+            | "footer"
+            | "form"
+            | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "head"
+            | "header"
+            | "hgroup"
+            | "hr"
+            | "html"
+            | "li"
+            | "link"
+            | "main"
+            | "menu"
+            | "meta"
+            | "nav"
+            | "noscript"
+            | "ol"
+            | "p"
+            | "pre"
+            | "script"
+            | "search"
+            | "section"
+            | "style"
+            | "summary"
+            | "table"
+            | "tbody"
+            | "td"
+            | "template"
+            | "tfoot"
+            | "th"
+            | "thead"
+            | "title"
+            | "tr"
+            | "ul"
+    )
+}
+
+/// Returns whether the foreign element with the given tag name is an
+/// [HTML integration point](https://html.spec.whatwg.org/multipage/parsing.html#html-integration-point)
+/// or a [MathML text integration point](https://html.spec.whatwg.org/multipage/parsing.html#mathml-text-integration-point),
+/// whose children are HTML rather than foreign content.
+fn is_html_integration_point(tag_name: &str) -> bool {
+    matches!(
+        tag_name,
+        "foreignObject" | "desc" | "title" | "mi" | "mo" | "mn" | "ms" | "mtext"
+    )
+}
+
+/// Returns whether the element with the given tag name has content where
+/// whitespace is significant.
+fn is_preformatted(tag_name: &str) -> bool {
+    matches!(
+        tag_name,
+        "pre" | "textarea" | "script" | "style" | "listing" | "plaintext" | "xmp"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_yaml_snapshot;
+
+    use crate::url::{DisallowedUrlAction, UrlPolicy};
+    use crate::*;
+
+    use super::*;
+
+    fn render_pretty(element: &HtmlElement) -> String {
+        HtmlElementRenderer::new()
+            .pretty()
+            .render_to_string(element)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_pretty_document() {
+        let element = html()
+            .lang("en")
+            .child(
+                head()
+                    .child(meta().charset("utf-8"))
+                    .child(title().child("Auk"))
+                    .child(link().rel("stylesheet").href("/style.css")),
+            )
+            .child(
+                body()
+                    .child(h1().child("Hello from Auk!"))
+                    .child(
+                        ul().child(li().child("One"))
+                            .child(li().child("Two"))
+                            .child(li().child("Three")),
+                    )
+                    .child(
+                        p().child("This paragraph has ")
+                            .child(em().child("inline"))
+                            .child(
+                                " content that is long enough to exceed the maximum line width.",
+                            ),
+                    ),
+            );
+
+        assert_yaml_snapshot!(render_pretty(&element));
+    }
+
+    #[test]
+    fn test_pretty_short_elements_stay_on_one_line() {
+        assert_yaml_snapshot!(render_pretty(
+            &ul().child(li().child("One")).child(li().child("Two"))
+        ));
+    }
+
+    #[test]
+    fn test_pretty_preserves_preformatted_content() {
+        let element = div()
+            .child(
+                pre().child(
+                    div()
+                        .class("line")
+                        .child("fn main() {\n    println!(\"Hello, world!\");\n}"),
+                ),
+            )
+            .child(
+                textarea()
+                    .name("message")
+                    .child("A textarea whose content must be preserved exactly as is."),
+            );
+
+        assert_yaml_snapshot!(render_pretty(&element));
+    }
+
+    #[test]
+    fn test_pretty_preserves_preformatted_content_when_parent_breaks() {
+        let code = "fn main() {\n    println!(\"Hello, world!\");\n}";
+        let message = "  A textarea whose content\nmust be preserved exactly as is.";
+        let element = section()
+            .child(h2().child("A heading that is long enough to break the section"))
+            .child(pre().child(div().class("line").child(code)))
+            .child(
+                form().child(
+                    div()
+                        .child(label().child("Message"))
+                        .child(textarea().name("message").child(message)),
+                ),
+            );
+
+        let html = render_pretty(&element);
+        assert!(html.contains(&format!("<pre><div class=\"line\">{code}</div></pre>")));
+        assert!(html.contains(&format!("<textarea name=\"message\">{message}</textarea>")));
+        assert_yaml_snapshot!(html);
+    }
+
+    #[test]
+    fn test_pretty_custom_options() {
+        let element = div().child(p().child("One")).child(p().child("Two"));
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .mode(RenderMode::Pretty(PrettyOptions {
+                indent: 4,
+                max_width: 10,
+            }))
+            .render_to_string(&element)
+            .unwrap());
+    }
+
+    fn render_minified(element: &HtmlElement) -> String {
+        HtmlElementRenderer::new()
+            .minified()
+            .render_to_string(element)
+            .unwrap()
+    }
+
+    fn minified_document() -> HtmlElement {
+        html()
+            .lang("en")
+            .child(
+                head()
+                    .child(meta().charset("utf-8"))
+                    .child(title().child("Auk"))
+                    .child(link().rel("stylesheet").href("/style.css")),
+            )
+            .child(
+                body()
+                    .class("page home")
+                    .child(h1().id("title").child("Hello from Auk!"))
+                    .child(p().child("First ").child(em().child("paragraph")))
+                    .child(p().child("Second paragraph"))
+                    .child(
+                        ul().child(li().child("One"))
+                            .child(li().child("Two"))
+                            .child(li().child(a().href("/three?a=b").child("Three"))),
+                    )
+                    .child(
+                        dl().child(dt().child("Term"))
+                            .child(dd().child("Definition")),
+                    )
+                    .child(
+                        table().child(
+                            tbody()
+                                .child(tr().child(td().child("A")).child(td().child("B")))
+                                .child(tr().child(td().child("C")).child(td().child("D"))),
+                        ),
+                    )
+                    .child(
+                        select()
+                            .name("choice")
+                            .child(option().value("1").child("One"))
+                            .child(option().value("2").child("Two")),
+                    )
+                    .child(a().href("/").child(p().child("A paragraph inside a link"))),
+            )
+    }
+
+    #[test]
+    fn test_minified_document() {
+        assert_yaml_snapshot!(render_minified(&minified_document()));
+    }
+
+    #[test]
+    fn test_minified_parses_back_to_an_equivalent_tree() {
+        let element = minified_document();
+
+        assert_eq!(
+            parser::parse_document(&render_minified(&element)),
+            parser::parse_document(
+                &HtmlElementRenderer::new()
+                    .render_to_string(&element)
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_minified_attributes() {
+        let element = form()
+            .child(
+                input()
+                    .type_("checkbox")
+                    .attr("checked", "checked")
+                    .attr("disabled", "")
+                    .attr("data-empty-quote", "'")
+                    .attr("data-spaces", "a b")
+                    .attr("data-equals", "a=b")
+                    .attr("data-backtick", "`")
+                    .attr("data-value", "true"),
+            )
+            .child(
+                button()
+                    .type_("submit")
+                    .attr("title", "Fish & Chips")
+                    .child("Submit"),
+            );
+
+        assert_yaml_snapshot!(render_minified(&element));
+    }
+
+    #[test]
+    fn test_minified_self_closing_elements_parse_back_to_an_equivalent_tree() {
+        use crate::svg::{self, SvgAttributes};
+
+        let element = html().child(
+            body().child(
+                svg::svg()
+                    .view_box("0 0 10 10")
+                    .child(svg::circle().cx("5").cy("5").r("4"))
+                    .child(
+                        svg::path()
+                            .attr("d", "M 0 0 L 10 10")
+                            .attr("data-path", "a/"),
+                    )
+                    .child(svg::rect().attr("hidden", "")),
+            ),
+        );
+
+        let minified = render_minified(&element);
+        assert!(minified.contains(r#"<circle cx=5 cy=5 r=4 />"#));
+
+        assert_eq!(
+            parser::parse_document(&minified),
+            parser::parse_document(
+                &HtmlElementRenderer::new()
+                    .render_to_string(&element)
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_minified_whitespace() {
+        let element = div()
+            .child("\n  ")
+            .child(
+                p().child("  Some   text\n  with ")
+                    .child(strong().child("inline"))
+                    .child(" content.  "),
+            )
+            .child("\n  ")
+            .child(span().child("A"))
+            .child(" ")
+            .child(span().child("B"))
+            .child("\n")
+            .child(pre().child("  preformatted\n    text  "))
+            .child(textarea().child("  Line one\n\n  Line two"))
+            .child(script().child("if (a  <  b) {\n  run();\n}"))
+            .child(style().child("p  >  a { color:  red; }"));
+
+        assert_yaml_snapshot!(render_minified(&element));
+    }
+
+    #[test]
+    fn test_minified_fragment_keeps_end_tags_of_last_child() {
+        let fragment = fragment().child(li().child("One")).child(li().child("Two"));
+
+        assert_eq!(
+            HtmlElementRenderer::new()
+                .minified()
+                .render_fragment_to_string(&fragment)
+                .unwrap(),
+            "<li>One<li>Two</li>"
+        );
+    }
+
+    #[test]
+    fn test_xml_feed() {
+        let item = |title: &str, link: &str| {
+            HtmlElement::new("item")
+                .child(HtmlElement::new("title").child(title.to_string()))
+                .child(HtmlElement::new("link").child(link.to_string()))
+        };
+
+        let element = HtmlElement::new("rss")
+            .attr("version", "2.0")
+            .attr("xmlns:atom", "http://www.w3.org/2005/Atom")
+            .child(
+                HtmlElement::new("channel")
+                    .child(HtmlElement::new("title").child("Fish & Chips <Blog>"))
+                    .child(
+                        HtmlElement::new("atom:link")
+                            .attr("href", "https://example.com/feed.xml?a=1&b=2")
+                            .attr("rel", "self"),
+                    )
+                    .child(item("First post", "https://example.com/first"))
+                    .child(item("Second post", "https://example.com/second")),
+            );
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .xml()
+            .pretty()
+            .render_to_string(&element)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_xml_svg() {
+        let element = svg()
+            .attr("xmlns", "http://www.w3.org/2000/svg")
+            .attr("xmlns:xlink", "http://www.w3.org/1999/xlink")
+            .attr("viewBox", "0 0 24 24")
+            .child(
+                HtmlElement::new("path")
+                    .attr("d", "M0 0h24v24H0z")
+                    .attr("fill", ""),
+            )
+            .child(HtmlElement::new("use").attr("xlink:href", "#icon"))
+            .child(style().child("path > use { fill: \"red\"; }"))
+            .child(CommentElement::new("-- not -- allowed -"));
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .syntax(Syntax::Xml(XmlOptions { declaration: false }))
+            .render_to_string(&element)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_xml_does_not_insert_doctype() {
+        let element = html()
+            .attr("xmlns", "http://www.w3.org/1999/xhtml")
+            .child(body().child(br()).child(p().attr("title", "a\nb")));
+
+        assert_eq!(
+            HtmlElementRenderer::new()
+                .xml()
+                .minified()
+                .render_to_string(&element)
+                .unwrap(),
+            r#"<?xml version="1.0" encoding="UTF-8"?><html xmlns="http://www.w3.org/1999/xhtml"><body><br/><p title="a&#10;b"/></body></html>"#
+        );
+    }
+
+    #[test]
+    fn test_xml_processing_instruction() {
+        let fragment = fragment()
+            .child(ProcessingInstructionElement::new(
+                "xml-stylesheet",
+                "href=\"style.xsl\" ?>",
+            ))
+            .child(HtmlElement::new("urlset"));
+
+        assert_eq!(
+            HtmlElementRenderer::new()
+                .syntax(Syntax::Xml(XmlOptions { declaration: false }))
+                .render_fragment_to_string(&fragment)
+                .unwrap(),
+            r#"<?xml-stylesheet href="style.xsl" ? >?><urlset/>"#
+        );
+    }
+
+    #[test]
+    fn test_foreign_elements() {
+        use crate::mathml::{self, MathMlAttributes};
+        use crate::svg::{self, SvgAttributes};
+
+        let element = div()
+            .child(
+                svg::svg()
+                    .view_box("0 0 24 24")
+                    .child(
+                        svg::defs().child(
+                            svg::linear_gradient()
+                                .id("gradient")
+                                .child(svg::stop().offset("0").stop_color("red")),
+                        ),
+                    )
+                    .child(svg::path().d("M0 0h24v24H0z").fill("url(#gradient)"))
+                    .child(svg::use_().xlink_href("#icon"))
+                    .child(svg::style().child("path > use { fill: red; }"))
+                    .child(svg::foreign_object().child(p().child(br()))),
+            )
+            .child(
+                mathml::math().display("block").child(
+                    mathml::mfrac()
+                        .child(mathml::mi().child("a"))
+                        .child(mathml::mspace()),
+                ),
+            )
+            .child(br());
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .render_to_string(&element)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_foreign_elements_parse_back_to_an_equivalent_tree() {
+        use crate::svg::{self, SvgAttributes};
+
+        let element = body().child(
+            svg::svg()
+                .view_box("0 0 10 10")
+                .child(svg::clip_path().id("clip").child(svg::rect().width("10")))
+                .child(svg::circle().cx("5").cy("5").r("4"))
+                .child(svg::text().child("a < b")),
+        );
+
+        let html = HtmlElementRenderer::new()
+            .render_to_string(&element)
+            .unwrap();
+        let parsed = parser::parse_document(&html);
+
+        assert_eq!(
+            HtmlElementRenderer::new()
+                .render_to_string(&parsed.children[1].clone().html().unwrap())
+                .unwrap(),
+            html
+        );
+    }
+
+    #[test]
+    fn test_render_to_io_writer() {
+        let element = div().class("outer").child(p().child("Hello, world!"));
+
+        let IoWriter(output) = HtmlElementRenderer::with_writer(IoWriter(Vec::new()))
+            .render(&element)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            HtmlElementRenderer::new()
+                .render_to_string(&element)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_render_to_fmt_writer() {
+        let element = div().class("outer").child(p().child("Hello, world!"));
+
+        let mut output = String::new();
+        HtmlElementRenderer::with_writer(FmtWriter(&mut output))
+            .render(&element)
+            .unwrap();
+
+        assert_eq!(
+            output,
+            HtmlElementRenderer::new()
+                .render_to_string(&element)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_render_propagates_writer_errors() {
+        struct FailingWriter;
+
+        impl std::io::Write for FailingWriter {
+            fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("connection reset"))
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let error = HtmlElementRenderer::with_writer(IoWriter(FailingWriter))
+            .render(&p().child("Hello, world!"))
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, RenderError::Write(err) if err.to_string() == "connection reset"));
+    }
+
+    #[test]
+    fn test_raw_text_elements_are_not_escaped() {
+        let element = head()
+            .child(
+                script()
+                    .type_("application/json")
+                    .child(r#"{"html":"<b>bold</b>","cmp":"a > b && b < c"}"#),
+            )
+            .child(style().child("ul > li::before { content: \"&\"; }"));
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .render_to_string(&element)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_raw_text_end_tags_are_neutralized() {
+        let element = div()
+            .child(script().child("let html = '</script><script>alert(\"XSS\")</SCRIPT>';"))
+            .child(script().child("let comment = '<!-- <script>';"))
+            .child(style().child("a::after { content: '</style><script>alert(1)</script>'; }"));
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .render_to_string(&element)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_raw_text_in_json_scripts_stays_valid_json() {
+        let json = r#"{"html":"</script><script>alert(1)</script>","comment":"<!-- x"}"#;
+
+        for script_type in ["application/json", "application/ld+json", "importmap"] {
+            let html = HtmlElementRenderer::new()
+                .render_to_string(&script().type_(script_type).child(json))
+                .unwrap();
+
+            let text = html
+                .strip_prefix(&format!("<script type=\"{script_type}\">"))
+                .and_then(|html| html.strip_suffix("</script>"))
+                .unwrap();
+
+            assert!(!text.to_ascii_lowercase().contains("</script"));
+            assert!(!text.contains("<!--"));
+            assert_eq!(
+                serde_json::from_str::<serde_json::Value>(text).unwrap(),
+                serde_json::from_str::<serde_json::Value>(json).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_raw_text_in_script_data_blocks_is_rejected() {
+        let element = script()
+            .type_("text/x-template")
+            .child("<p>Safe</p><!-- unsafe -->");
+
+        assert!(matches!(
+            HtmlElementRenderer::new().render_to_string(&element),
+            Err(RenderError::UnescapableScriptData { script_type }) if script_type == "text/x-template"
+        ));
+
+        let element = script().type_("text/x-template").child("<p>{{ name }}</p>");
+        assert_eq!(
+            HtmlElementRenderer::new()
+                .render_to_string(&element)
+                .unwrap(),
+            r#"<script type="text/x-template"><p>{{ name }}</p></script>"#
+        );
+    }
+
+    #[test]
+    fn test_escapable_raw_text_elements_are_escaped() {
+        let element = div()
+            .child(title().child("Fish & Chips </title>"))
+            .child(textarea().child("</textarea><script>alert('XSS')</script>"));
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .render_to_string(&element)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_leading_newline_is_preserved() {
+        let element = div()
+            .child(pre().child("\nindented\n"))
+            .child(textarea().child("\n\nTwo newlines"))
+            .child(pre().child("no leading newline"));
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .render_to_string(&element)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_comments() {
+        let element = div()
+            .child(CommentElement::new(" Built by Auk "))
+            .child(p().child("Excerpt"))
+            .child(CommentElement::new(" more "))
+            .child(p().child("The rest of the post."));
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .render_to_string(&element)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_comment_terminators_are_escaped() {
+        let element = div()
+            .child(CommentElement::new("--><script>alert('XSS')</script><!--"))
+            .child(CommentElement::new("--!><script>alert('XSS')</script>"))
+            .child(CommentElement::new("> starts with a greater-than sign"))
+            .child(CommentElement::new("-> starts with an arrow"))
+            .child(CommentElement::new("ends with <!-"));
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .render_to_string(&element)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_doctype_processing_instruction_and_cdata() {
+        let element = svg()
+            .child(ProcessingInstructionElement::new(
+                "xml-stylesheet",
+                "href=\"style.css\"",
+            ))
+            .child(CDataElement::new("a > b { fill: red; } ]]>"))
+            .child(HtmlElement::new("desc").child(CDataElement::new("a > b { fill: red; } ]]>")));
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .render_to_string(&element)
+            .unwrap());
+
+        let mut renderer = HtmlElementRenderer::new();
+        renderer
+            .visit_doctype(&DoctypeElement {
+                name: "html".to_string(),
+                public_id: Some("-//W3C//DTD XHTML 1.0 Strict//EN".to_string()),
+                system_id: Some("http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd".to_string()),
+            })
+            .unwrap();
+        renderer.visit(&html()).unwrap();
+
+        assert_yaml_snapshot!(renderer.html());
+    }
+
+    #[test]
+    fn test_cdata_outside_of_foreign_content_is_escaped() {
+        let element = div()
+            .child(CDataElement::new("x><img src=x onerror=alert(1)>"))
+            .child(
+                svg()
+                    .child(CDataElement::new("a > b"))
+                    .child(HtmlElement::new("foreignObject").child(CDataElement::new("<b>"))),
+            );
+
+        let html = HtmlElementRenderer::new()
+            .render_to_string(&element)
+            .unwrap();
+        assert_eq!(
+            html,
+            "<div>x&gt;&lt;img src=x onerror=alert(1)&gt;<svg><![CDATA[a > b]]><foreignObject>&lt;b&gt;</foreignObject></svg></div>"
+        );
+
+        use crate::selector::{Select, Selector};
+
+        let img = Selector::parse("img").unwrap();
+        assert!(parser::parse_fragment(&html).select(&img).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_doctypes_are_rejected() {
+        let render = |doctype: DoctypeElement| {
+            HtmlElementRenderer::new().render_fragment_to_string(&fragment().child(doctype))
+        };
+
+        assert!(matches!(
+            render(DoctypeElement::new("html><script>alert(1)</script")),
+            Err(RenderError::InvalidDoctypeName(_))
+        ));
+        assert!(matches!(
+            render(DoctypeElement::new("")),
+            Err(RenderError::InvalidDoctypeName(_))
+        ));
+
+        for id in ["a\"b'c", "a>b"] {
+            let doctype = DoctypeElement {
+                name: "html".to_string(),
+                public_id: Some(id.to_string()),
+                system_id: None,
+            };
+            assert!(
+                matches!(render(doctype), Err(RenderError::InvalidDoctypeId(invalid)) if invalid == id)
+            );
+
+            let doctype = DoctypeElement {
+                name: "html".to_string(),
+                public_id: None,
+                system_id: Some(id.to_string()),
+            };
+            assert!(
+                matches!(render(doctype), Err(RenderError::InvalidDoctypeId(invalid)) if invalid == id)
+            );
+        }
+
+        let doctype = DoctypeElement {
+            name: "html".to_string(),
+            public_id: None,
+            system_id: Some("about:\"legacy-compat\"".to_string()),
+        };
+        assert_eq!(
+            render(doctype).unwrap(),
+            r#"<!DOCTYPE html SYSTEM 'about:"legacy-compat"'>"#
+        );
+    }
+
+    #[test]
+    fn test_invalid_processing_instruction_targets_are_rejected() {
+        for target in ["", "xml stylesheet", "a>b", "1abc", "x?>"] {
+            let element = div().child(ProcessingInstructionElement::new(target, "data"));
+
+            assert!(matches!(
+                HtmlElementRenderer::new().render_to_string(&element),
+                Err(RenderError::InvalidProcessingInstructionTarget(invalid)) if invalid == target
+            ));
+            assert!(matches!(
+                HtmlElementRenderer::new().xml().render_to_string(&element),
+                Err(RenderError::InvalidProcessingInstructionTarget(invalid)) if invalid == target
+            ));
+        }
+    }
+
+    #[test]
+    fn test_render_fragment() {
+        let fragment = fragment()
+            .child(DoctypeElement::html())
+            .child(html().child(body().child(p().child("Hello, world!"))));
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .render_fragment_to_string(&fragment)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_pretty_render_fragment() {
+        let fragment = fragment()
+            .child(CommentElement::new(" Header "))
+            .child(header().child(h1().child("Auk")))
+            .child(main().child(p().child("Hello, world!")));
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .pretty()
+            .render_fragment_to_string(&fragment)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_invalid_tag_names_are_rejected() {
+        for tag_name in ["", "1div", "div onclick=alert(1)", "div>", "a/b", "<div"] {
+            let result = HtmlElementRenderer::new().render_to_string(&HtmlElement::new(tag_name));
+
+            assert!(
+                matches!(&result, Err(RenderError::InvalidTagName(name)) if name == tag_name),
+                "expected {tag_name:?} to be rejected, got {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_attribute_names_are_rejected() {
+        for attr_name in [
+            "",
+            "onclick=\"alert(1)\"",
+            "data-a b",
+            "x>",
+            "a/b",
+            "\"",
+            "'",
+            "x\u{0}",
+            "x\u{FFFF}",
+        ] {
+            let result =
+                HtmlElementRenderer::new().render_to_string(&div().attr(attr_name, "value"));
+
+            assert!(
+                matches!(&result, Err(RenderError::InvalidAttributeName(name)) if name == attr_name),
+                "expected {attr_name:?} to be rejected, got {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_valid_names_are_accepted() {
+        let element = HtmlElement::new("my-element")
+            .attr("data-user.name", "auk")
+            .attr("@click", "toggle")
+            .attr(":class", "active")
+            .attr("xlink:href", "#icon")
+            .child(HtmlElement::new("foreignObject"));
+
+        assert_eq!(
+            HtmlElementRenderer::new()
+                .render_to_string(&element)
+                .unwrap(),
+            r##"<my-element data-user.name="auk" @click="toggle" :class="active" xlink:href="#icon"><foreignObject></foreignObject></my-element>"##
+        );
+    }
+
+    #[test]
+    fn test_url_policy_neutralizes_disallowed_urls() {
+        let element = div()
+            .child(a().href("javascript:alert('XSS')").child("Click me"))
+            .child(a().href("https://example.com").child("Example"))
+            .child(a().href("/about").child("About"))
+            .child(img().src("data:image/svg+xml,<svg onload=alert(1)>"))
+            .child(form().action(" JaVaScRiPt:alert(1)"))
+            .child(video().attr("poster", "java\tscript:alert(1)"))
+            .child(img().attr("srcset", "a.png 1x, javascript:alert(1) 2x"));
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .url_policy(UrlPolicy::default())
+            .render_to_string(&element)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_url_policy_rejects_disallowed_urls() {
+        let result = HtmlElementRenderer::new()
+            .url_policy(UrlPolicy::default().on_disallowed(DisallowedUrlAction::Reject))
+            .render_to_string(&p().child(a().href("javascript:alert('XSS')")));
+
+        assert!(matches!(
+            result,
+            Err(RenderError::DisallowedUrl { attribute, url })
+                if attribute == "href" && url == "javascript:alert('XSS')"
+        ));
+    }
 }
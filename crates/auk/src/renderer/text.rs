@@ -0,0 +1,477 @@
+use std::borrow::Cow;
+use std::convert::Infallible;
+
+use crate::visitor::{walk_element, Visitor};
+use crate::{Fragment, HtmlElement};
+
+use super::is_block;
+
+/// A renderer for [`HtmlElement`]s to plain text.
+///
+/// The text is laid out so that it is readable on its own:
+///
+/// - Block-level elements start on a new line, and paragraphs, headings,
+///   lists, and tables are separated by a blank line.
+/// - List items are prefixed with a bullet (`-`) or, in ordered lists, their
+///   number. Nested lists are indented.
+/// - Links are followed by their URL in brackets (e.g., `docs [https://docs.rs/auk]`).
+/// - Table cells are separated by ` | `, with each row on its own line.
+/// - Images are replaced with their `alt` text.
+/// - Whitespace is collapsed, except inside of `<pre>` and `<textarea>`.
+///
+/// The contents of `<head>`, `<script>`, `<style>`, and `<template>` are
+/// skipped. Safe text has any tags stripped and its character references
+/// (e.g., `&nbsp;`) decoded.
+///
+/// ```
+/// use auk::*;
+/// use auk::renderer::TextRenderer;
+///
+/// let element = div()
+///     .child(h1().child("Auk"))
+///     .child(
+///         p().child("Read the ")
+///             .child(a().href("https://docs.rs/auk").child("docs"))
+///             .child("."),
+///     );
+///
+/// assert_eq!(
+///     TextRenderer::new().render_to_string(&element),
+///     "Auk\n\nRead the docs [https://docs.rs/auk]."
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct TextRenderer {
+    text: String,
+    /// The number of line breaks to write before the next text.
+    pending_breaks: usize,
+    /// Whether to write a space before the next text.
+    pending_space: bool,
+    /// The list item marker to write before the next text.
+    pending_marker: Option<String>,
+    /// Whether text has been written to the current line.
+    mid_line: bool,
+    /// The number of preformatted elements the text being rendered is inside of.
+    preformatted_depth: usize,
+    /// The lists being rendered, with the number of the next item for ordered lists.
+    lists: Vec<Option<usize>>,
+    /// The positions in the text where the text of the links being rendered
+    /// starts, once it has been written.
+    links: Vec<Option<usize>>,
+    /// Whether the next table cell is the first in its row.
+    first_cell: bool,
+}
+
+impl TextRenderer {
+    /// Returns a new [`TextRenderer`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the rendered text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Renders the given [`HtmlElement`] to a string of plain text.
+    pub fn render_to_string(mut self, element: &HtmlElement) -> String {
+        self.visit(element).unwrap_or_else(|never| match never {});
+        self.text
+    }
+
+    /// Renders the given [`Fragment`] to a string of plain text.
+    pub fn render_fragment_to_string(mut self, fragment: &Fragment) -> String {
+        self.visit_fragment(fragment)
+            .unwrap_or_else(|never| match never {});
+        self.text
+    }
+
+    /// Ensures that the next text is preceded by at least the given number of
+    /// line breaks.
+    fn break_lines(&mut self, breaks: usize) {
+        self.pending_breaks = self.pending_breaks.max(breaks);
+    }
+
+    /// Writes the whitespace, indentation, and list marker that precede the
+    /// next text.
+    fn flush(&mut self) {
+        if self.text.is_empty() {
+            self.pending_breaks = 0;
+        }
+
+        if self.pending_breaks > 0 {
+            for _ in 0..self.pending_breaks {
+                self.text.push('\n');
+            }
+
+            self.pending_breaks = 0;
+            self.mid_line = false;
+        }
+
+        if !self.mid_line {
+            let indent = if self.pending_marker.is_some() {
+                self.lists.len().saturating_sub(1)
+            } else {
+                self.lists.len()
+            };
+
+            for _ in 0..indent {
+                self.text.push_str("  ");
+            }
+
+            if let Some(marker) = self.pending_marker.take() {
+                self.text.push_str(&marker);
+            }
+
+            self.mid_line = true;
+            self.pending_space = false;
+        } else if std::mem::take(&mut self.pending_space) {
+            self.text.push(' ');
+        }
+
+        for start in &mut self.links {
+            start.get_or_insert(self.text.len());
+        }
+    }
+
+    /// Returns the number of line breaks that should surround the element with
+    /// the given tag name, if it is a block.
+    fn block_breaks(&self, tag_name: &str) -> Option<usize> {
+        match tag_name {
+            "td" | "th" => None,
+            "ul" | "ol" | "menu" if !self.lists.is_empty() => Some(1),
+            "p" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "ul" | "ol" | "menu" | "pre"
+            | "blockquote" | "table" | "dl" | "figure" | "hr" => Some(2),
+            tag_name if is_block(tag_name) => Some(1),
+            _ => None,
+        }
+    }
+
+    fn write_text(&mut self, text: &str) {
+        for char in text.chars() {
+            if self.preformatted_depth > 0 {
+                if char == '\n' {
+                    self.flush();
+                    self.text.push('\n');
+                    self.mid_line = false;
+                    continue;
+                }
+            } else if char.is_ascii_whitespace() {
+                self.pending_space = true;
+                continue;
+            }
+
+            self.flush();
+            self.text.push(char);
+        }
+    }
+}
+
+impl Visitor for TextRenderer {
+    type Error = Infallible;
+
+    fn visit(&mut self, element: &HtmlElement) -> Result<(), Self::Error> {
+        if matches!(
+            element.tag_name.as_str(),
+            "head" | "script" | "style" | "template"
+        ) {
+            return Ok(());
+        }
+
+        walk_element(self, element)
+    }
+
+    fn enter(&mut self, element: &HtmlElement) -> Result<(), Self::Error> {
+        if let Some(breaks) = self.block_breaks(&element.tag_name) {
+            self.break_lines(breaks);
+        }
+
+        match element.tag_name.as_str() {
+            "br" => self.pending_breaks += 1,
+            "pre" | "textarea" => self.preformatted_depth += 1,
+            "ul" | "menu" => self.lists.push(None),
+            "ol" => {
+                let start = element
+                    .attrs
+                    .get("start")
+                    .and_then(|start| start.parse().ok())
+                    .unwrap_or(1);
+
+                self.lists.push(Some(start));
+            }
+            "li" => {
+                // Write the marker of an item that is still empty, so that it
+                // is not lost.
+                if self.pending_marker.is_some() {
+                    self.flush();
+                    self.break_lines(1);
+                }
+
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        let marker = format!("{number}. ");
+                        *number += 1;
+                        marker
+                    }
+                    _ => "- ".to_string(),
+                };
+
+                self.pending_marker = Some(marker);
+            }
+            "tr" => self.first_cell = true,
+            "td" | "th" => {
+                if !self.first_cell {
+                    self.flush();
+                    self.text.push_str(" | ");
+                }
+
+                self.first_cell = false;
+            }
+            "a" => self.links.push(None),
+            "img" => {
+                if let Some(alt) = element.attrs.get("alt") {
+                    self.pending_space = true;
+                    self.write_text(alt);
+                    self.pending_space = true;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn leave(&mut self, element: &HtmlElement) -> Result<(), Self::Error> {
+        match element.tag_name.as_str() {
+            "pre" | "textarea" => self.preformatted_depth -= 1,
+            "ul" | "menu" | "ol" => {
+                self.lists.pop();
+            }
+            "a" => {
+                let link_text = self
+                    .links
+                    .pop()
+                    .flatten()
+                    .map_or("", |start| &self.text[start..]);
+
+                if let Some(href) = element.attrs.get("href") {
+                    if !href.is_empty() && !href.starts_with('#') && link_text != href {
+                        self.write_text(&format!(" [{href}]"));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(breaks) = self.block_breaks(&element.tag_name) {
+            self.break_lines(breaks);
+        }
+
+        Ok(())
+    }
+
+    fn visit_text(&mut self, text: &str, safe: bool) -> Result<(), Self::Error> {
+        if safe {
+            let text = strip_tags(text);
+            self.write_text(&decode_character_references(&text));
+        } else {
+            self.write_text(text);
+        }
+
+        Ok(())
+    }
+
+    fn visit_cdata(&mut self, text: &str) -> Result<(), Self::Error> {
+        self.write_text(text);
+
+        Ok(())
+    }
+}
+
+/// Strips any tags from the given HTML, leaving only its text.
+fn strip_tags(html: &str) -> Cow<'_, str> {
+    if !html.contains('<') {
+        return Cow::Borrowed(html);
+    }
+
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for char in html.chars() {
+        match char {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            char if !in_tag => text.push(char),
+            _ => {}
+        }
+    }
+
+    Cow::Owned(text)
+}
+
+/// Decodes the character references (e.g., `&nbsp;` or `&#8212;`) in the given text.
+///
+/// Only the most common named character references are supported. Any that
+/// are not are left as-is.
+fn decode_character_references(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find('&') {
+        decoded.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        let reference = rest.find(';').map(|end| &rest[1..end]);
+        match reference.and_then(|reference| Some((reference, decode_reference(reference)?))) {
+            Some((reference, char)) => {
+                decoded.push(char);
+                rest = &rest[reference.len() + 2..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+
+    Cow::Owned(decoded)
+}
+
+/// Decodes the given character reference, without the surrounding `&` and `;`.
+fn decode_reference(reference: &str) -> Option<char> {
+    if let Some(number) = reference.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+
+        return char::from_u32(code);
+    }
+
+    Some(match reference {
+        "nbsp" => '\u{a0}',
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "hellip" => '…',
+        "mdash" => '—',
+        "ndash" => '–',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "bull" => '•',
+        "middot" => '·',
+        "times" => '×',
+        "deg" => '°',
+        "euro" => '€',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_yaml_snapshot;
+
+    use crate::*;
+
+    use super::*;
+
+    fn render_text(element: &HtmlElement) -> String {
+        TextRenderer::new().render_to_string(element)
+    }
+
+    #[test]
+    fn test_render_document() {
+        let element =
+            html()
+                .child(
+                    head()
+                        .child(title().child("Auk"))
+                        .child(style().child("body { margin: 0; }")),
+                )
+                .child(
+                    body()
+                        .child(h1().child("Hello from   Auk!"))
+                        .child(
+                            p().child("Auk is an ")
+                                .child(a().href("https://example.com/edsl").child("eDSL"))
+                                .child(" for writing\n    HTML.")
+                                .child(br())
+                                .child("See the ")
+                                .child(a().href("#usage").child("usage"))
+                                .child(" section."),
+                        )
+                        .child(
+                            ul().child(li().child("One"))
+                                .child(li().child("Two").child(
+                                    ol().child(li().child("Nested")).child(li().child("Items")),
+                                ))
+                                .child(li().child(
+                                    a().href("https://example.com").child("https://example.com"),
+                                )),
+                        )
+                        .child(
+                            ol().start("3")
+                                .child(li().child("Three"))
+                                .child(li().child("Four")),
+                        )
+                        .child(
+                            table()
+                                .child(thead().child(
+                                    tr().child(th().child("Name")).child(th().child("Value")),
+                                ))
+                                .child(
+                                    tbody()
+                                        .child(tr().child(td().child("a")).child(td().child("1")))
+                                        .child(tr().child(td().child("b")).child(td().child("2"))),
+                                ),
+                        )
+                        .child(pre().child("fn main() {\n    println!(\"Hello!\");\n}"))
+                        .child(p().child(img().src("/auk.png").alt("An auk")).child(
+                            TextElement::safe_static(
+                                "Fish&nbsp;&amp;&nbsp;Chips &mdash; <em>&#169;</em> 2024",
+                            ),
+                        ))
+                        .child(script().child("console.log('hidden');")),
+                );
+
+        assert_yaml_snapshot!(render_text(&element));
+    }
+
+    #[test]
+    fn test_render_fragment() {
+        let fragment = fragment()
+            .child(p().child("First"))
+            .child(CommentElement::new(" hidden "))
+            .child(div().child("Second"))
+            .child(span().child("inline"))
+            .child(" text");
+
+        assert_eq!(
+            TextRenderer::new().render_fragment_to_string(&fragment),
+            "First\n\nSecond\ninline text"
+        );
+    }
+
+    #[test]
+    fn test_decode_character_references() {
+        assert_eq!(
+            decode_character_references("a&nbsp;b &amp; &#x41;&#66; &unknown; & done"),
+            "a\u{a0}b & AB &unknown; & done"
+        );
+    }
+}
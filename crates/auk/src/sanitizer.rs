@@ -0,0 +1,434 @@
+//! Constructs for sanitizing untrusted trees of [`HtmlElement`]s.
+
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::mem;
+
+use crate::url::UrlPolicy;
+use crate::visitor::MutVisitor;
+use crate::{Element, Fragment, HtmlElement};
+
+/// An allowlist-based HTML sanitizer.
+///
+/// Any element, attribute, or URL that is not explicitly allowed is removed:
+///
+/// - Elements that are not allowed are unwrapped, with their (sanitized)
+///   children taking their place. Elements whose content is dangerous or
+///   meaningless on its own (e.g., `<script>` and `<style>`) are removed
+///   along with their children.
+/// - Attributes that are not allowed for the element they appear on are
+///   removed. This includes all event handler attributes (e.g., `onclick`).
+/// - Attributes containing a URL that is not allowed by the [`UrlPolicy`]
+///   are removed.
+/// - `style` attributes, if allowed, are removed when they contain CSS
+///   expressions or scripts.
+/// - Comments are removed unless allowed, and document type declarations,
+///   processing instructions, and CDATA sections are always removed.
+/// - Safe text (which would otherwise be written verbatim) is marked as
+///   unsafe so that it is escaped when rendered.
+///
+/// The [`Default`] sanitizer allows a set of elements and attributes suitable
+/// for content produced from Markdown.
+#[derive(Debug, Clone)]
+pub struct Sanitizer {
+    allowed_tags: HashSet<String>,
+    allowed_attributes: HashMap<String, HashSet<String>>,
+    generic_attributes: HashSet<String>,
+    removed_tags: HashSet<String>,
+    url_policy: UrlPolicy,
+    allow_comments: bool,
+}
+
+impl Default for Sanitizer {
+    fn default() -> Self {
+        Self::new()
+            .allow_tags([
+                "a",
+                "abbr",
+                "b",
+                "blockquote",
+                "br",
+                "caption",
+                "cite",
+                "code",
+                "dd",
+                "del",
+                "details",
+                "div",
+                "dl",
+                "dt",
+                "em",
+                "figcaption",
+                "figure",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "hr",
+                "i",
+                "img",
+                "ins",
+                "kbd",
+                "li",
+                "mark",
+                "ol",
+                "p",
+                "pre",
+                "q",
+                "s",
+                "samp",
+                "section",
+                "small",
+                "span",
+                "strong",
+                "sub",
+                "summary",
+                "sup",
+                "table",
+                "tbody",
+                "td",
+                "tfoot",
+                "th",
+                "thead",
+                "tr",
+                "u",
+                "ul",
+                "var",
+            ])
+            .allow_generic_attributes(["class", "dir", "id", "lang", "title"])
+            .allow_attributes("a", ["href", "hreflang", "rel"])
+            .allow_attributes("blockquote", ["cite"])
+            .allow_attributes("del", ["cite", "datetime"])
+            .allow_attributes("img", ["alt", "height", "src", "width"])
+            .allow_attributes("ins", ["cite", "datetime"])
+            .allow_attributes("li", ["value"])
+            .allow_attributes("ol", ["reversed", "start", "type"])
+            .allow_attributes("q", ["cite"])
+            .allow_attributes("td", ["align", "colspan", "headers", "rowspan"])
+            .allow_attributes("th", ["align", "colspan", "headers", "rowspan", "scope"])
+    }
+}
+
+impl Sanitizer {
+    /// Returns a new [`Sanitizer`] that does not allow any elements or
+    /// attributes.
+    ///
+    /// Use [`Sanitizer::default`] for a sanitizer with sensible defaults.
+    pub fn new() -> Self {
+        Self {
+            allowed_tags: HashSet::new(),
+            allowed_attributes: HashMap::new(),
+            generic_attributes: HashSet::new(),
+            removed_tags: [
+                "embed", "frame", "iframe", "math", "noembed", "noframes", "noscript", "object",
+                "script", "select", "style", "svg", "template", "textarea", "title",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+            url_policy: UrlPolicy::default(),
+            allow_comments: false,
+        }
+    }
+
+    /// Allows the element with the given tag name.
+    pub fn allow_tag(mut self, tag: impl Into<String>) -> Self {
+        self.allowed_tags.insert(tag.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Allows the elements with the given tag names.
+    pub fn allow_tags(self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        tags.into_iter().fold(self, Self::allow_tag)
+    }
+
+    /// Allows the given attributes on the element with the given tag name.
+    pub fn allow_attributes(
+        mut self,
+        tag: impl Into<String>,
+        names: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_attributes
+            .entry(tag.into().to_ascii_lowercase())
+            .or_default()
+            .extend(
+                names
+                    .into_iter()
+                    .map(|name| name.into().to_ascii_lowercase()),
+            );
+        self
+    }
+
+    /// Allows the given attributes on all allowed elements.
+    pub fn allow_generic_attributes(
+        mut self,
+        names: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.generic_attributes.extend(
+            names
+                .into_iter()
+                .map(|name| name.into().to_ascii_lowercase()),
+        );
+        self
+    }
+
+    /// Removes the elements with the given tag names along with their
+    /// children, rather than unwrapping them, when they are not allowed.
+    pub fn remove_tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.removed_tags
+            .extend(tags.into_iter().map(|tag| tag.into().to_ascii_lowercase()));
+        self
+    }
+
+    /// Sets the [`UrlPolicy`] used to check URL-bearing attributes.
+    ///
+    /// Attributes containing disallowed URLs are always removed, regardless of
+    /// the policy's [`DisallowedUrlAction`](crate::url::DisallowedUrlAction).
+    pub fn url_policy(mut self, url_policy: UrlPolicy) -> Self {
+        self.url_policy = url_policy;
+        self
+    }
+
+    /// Sets whether comments are allowed.
+    pub fn allow_comments(mut self, allow_comments: bool) -> Self {
+        self.allow_comments = allow_comments;
+        self
+    }
+
+    /// Returns whether the element with the given tag name is allowed.
+    pub fn is_tag_allowed(&self, tag: &str) -> bool {
+        self.allowed_tags.contains(&tag.to_ascii_lowercase())
+    }
+
+    /// Returns whether the attribute with the given name and value is allowed
+    /// on the element with the given tag name.
+    pub fn is_attribute_allowed(&self, tag: &str, name: &str, value: &str) -> bool {
+        let name = name.to_ascii_lowercase();
+
+        let is_allowed = self.generic_attributes.contains(&name)
+            || self
+                .allowed_attributes
+                .get(&tag.to_ascii_lowercase())
+                .is_some_and(|names| names.contains(&name));
+        if !is_allowed {
+            return false;
+        }
+
+        if name == "style" && is_dangerous_style(value) {
+            return false;
+        }
+
+        self.url_policy.is_attribute_allowed(&name, value)
+    }
+
+    /// Sanitizes the given [`Fragment`] in place.
+    pub fn sanitize(&self, fragment: &mut Fragment) {
+        SanitizeVisitor { sanitizer: self }
+            .visit_fragment(fragment)
+            .unwrap_or_else(|never| match never {})
+    }
+
+    /// Sanitizes the given [`HtmlElement`], returning what is left of it.
+    ///
+    /// The result is a [`Fragment`], as the element itself may be unwrapped or
+    /// removed.
+    pub fn sanitize_element(&self, element: HtmlElement) -> Fragment {
+        let mut fragment = Fragment::from(vec![Element::Html(element)]);
+        self.sanitize(&mut fragment);
+        fragment
+    }
+}
+
+/// Returns whether the given `style` attribute value contains anything that
+/// could execute script.
+fn is_dangerous_style(style: &str) -> bool {
+    let style = style
+        .chars()
+        .filter(|char| !char.is_whitespace() && *char != '\\')
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    [
+        "expression(",
+        "javascript:",
+        "vbscript:",
+        "-moz-binding",
+        "behavior:",
+    ]
+    .iter()
+    .any(|pattern| style.contains(pattern))
+}
+
+/// The [`MutVisitor`] that performs the sanitization for a [`Sanitizer`].
+///
+/// This is kept private as it only filters the children of the elements it
+/// visits, so visiting an element directly would skip checking the element's
+/// own tag. [`Sanitizer::sanitize`] always starts from a [`Fragment`] instead,
+/// so every element is treated as a child.
+struct SanitizeVisitor<'a> {
+    sanitizer: &'a Sanitizer,
+}
+
+impl MutVisitor for SanitizeVisitor<'_> {
+    type Error = Infallible;
+
+    fn visit(&mut self, element: &mut HtmlElement) -> Result<(), Self::Error> {
+        let sanitizer = self.sanitizer;
+        let tag_name = element.tag_name.clone();
+        element
+            .attrs
+            .retain(|name, value| sanitizer.is_attribute_allowed(&tag_name, name, value));
+
+        self.visit_children(&mut element.children)
+    }
+
+    fn visit_children(&mut self, children: &mut Vec<Element>) -> Result<(), Self::Error> {
+        let mut sanitized = Vec::with_capacity(children.len());
+
+        for child in mem::take(children) {
+            match child {
+                Element::Html(mut element) => {
+                    if self.sanitizer.is_tag_allowed(&element.tag_name) {
+                        self.visit(&mut element)?;
+                        sanitized.push(Element::Html(element));
+                    } else if !self
+                        .sanitizer
+                        .removed_tags
+                        .contains(&element.tag_name.to_ascii_lowercase())
+                    {
+                        self.visit_children(&mut element.children)?;
+                        sanitized.extend(element.children);
+                    }
+                }
+                Element::Text(mut text) => {
+                    text.safe = false;
+                    sanitized.push(Element::Text(text));
+                }
+                Element::Comment(comment) if self.sanitizer.allow_comments => {
+                    sanitized.push(Element::Comment(comment));
+                }
+                Element::Comment(_)
+                | Element::Doctype(_)
+                | Element::ProcessingInstruction(_)
+                | Element::CData(_) => {}
+            }
+        }
+
+        *children = sanitized;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_yaml_snapshot;
+
+    use crate::renderer::HtmlElementRenderer;
+    use crate::*;
+
+    use super::*;
+
+    fn sanitize(sanitizer: &Sanitizer, element: HtmlElement) -> String {
+        HtmlElementRenderer::new()
+            .render_fragment_to_string(&sanitizer.sanitize_element(element))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sanitize_removes_dangerous_content() {
+        let element = div()
+            .class("content")
+            .attr("onclick", "alert(1)")
+            .child(script().child("alert(1)"))
+            .child(style().child("body { display: none; }"))
+            .child(
+                p().attr("onmouseover", "alert(1)")
+                    .child("Hello, ")
+                    .child(a().href("javascript:alert(1)").child("world"))
+                    .child(a().href("https://example.com").child("!")),
+            )
+            .child(
+                img()
+                    .src("data:image/svg+xml,<svg onload=alert(1)>")
+                    .alt("Image"),
+            )
+            .child(CommentElement::new(" secret "))
+            .child(TextElement {
+                text: "<img src=x onerror=alert(1)>".to_string(),
+                safe: true,
+            });
+
+        assert_yaml_snapshot!(sanitize(&Sanitizer::default(), element));
+    }
+
+    #[test]
+    fn test_sanitize_unwraps_disallowed_elements() {
+        let element = article().child(
+            form()
+                .action("/login")
+                .child(label().child("Name: ").child(em().child("required")))
+                .child(input().type_("text")),
+        );
+
+        assert_yaml_snapshot!(sanitize(&Sanitizer::default(), element));
+    }
+
+    #[test]
+    fn test_sanitize_style_attribute() {
+        let sanitizer = Sanitizer::default().allow_generic_attributes(["style"]);
+
+        let element = div()
+            .child(p().attr("style", "color: red"))
+            .child(p().attr("style", "width: expression(alert(1))"))
+            .child(p().attr("style", "background: url(JaVa\\Script:alert(1))"));
+
+        assert_yaml_snapshot!(sanitize(&sanitizer, element));
+    }
+
+    #[test]
+    fn test_sanitize_custom_policy() {
+        let sanitizer = Sanitizer::new()
+            .allow_tags(["p", "a"])
+            .allow_attributes("a", ["href"])
+            .url_policy(UrlPolicy::new().allow_scheme("https").url_attribute("href"))
+            .allow_comments(true);
+
+        let element = p()
+            .class("lead")
+            .child(CommentElement::new(" kept "))
+            .child(a().href("https://example.com").title("Example"))
+            .child(a().href("http://example.com"))
+            .child(strong().child("Bold"));
+
+        assert_yaml_snapshot!(sanitize(&sanitizer, element));
+    }
+
+    #[test]
+    fn test_sanitize_checks_root_element() {
+        let sanitizer = Sanitizer::default();
+
+        assert_eq!(
+            sanitize(
+                &sanitizer,
+                form().action("/login").child(p().child("Log in"))
+            ),
+            "<p>Log in</p>"
+        );
+        assert_eq!(sanitize(&sanitizer, script().child("alert(1)")), "");
+
+        let mut fragment = Fragment::from(vec![
+            Element::Html(iframe().src("https://example.com")),
+            Element::Html(em().attr("onclick", "alert(1)").child("Hi")),
+        ]);
+        sanitizer.sanitize(&mut fragment);
+        assert_eq!(
+            HtmlElementRenderer::new()
+                .render_fragment_to_string(&fragment)
+                .unwrap(),
+            "<em>Hi</em>"
+        );
+    }
+}
@@ -0,0 +1,688 @@
+//! Constructs for querying trees of [`HtmlElement`]s using CSS selectors.
+//!
+//! A practical subset of [CSS selectors](https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_selectors)
+//! is supported:
+//!
+//! - Type (`p`) and universal (`*`) selectors
+//! - Class (`.lead`) and ID (`#main`) selectors
+//! - Attribute selectors (`[href]` and `[type="text"]`)
+//! - Descendant (`ul li`) and child (`ul > li`) combinators
+//! - The `:first-child` and `:not()` pseudo-classes
+//! - Selector lists (`h1, h2`)
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use indexmap::IndexMap;
+
+use crate::{Element, Fragment, HtmlElement};
+
+/// An error that occurred while parsing a [`Selector`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectorError {
+    /// The selector ended unexpectedly.
+    UnexpectedEnd,
+
+    /// The selector contained an unexpected character at the given byte offset.
+    UnexpectedChar(char, usize),
+
+    /// The selector used a pseudo-class that is not supported.
+    UnsupportedPseudoClass(String),
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of selector"),
+            Self::UnexpectedChar(char, offset) => {
+                write!(f, "unexpected character {char:?} at offset {offset}")
+            }
+            Self::UnsupportedPseudoClass(name) => write!(f, "unsupported pseudo-class `:{name}`"),
+        }
+    }
+}
+
+impl Error for SelectorError {}
+
+/// A parsed CSS selector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    alternatives: Vec<ComplexSelector>,
+}
+
+impl Selector {
+    /// Parses the given CSS selector.
+    pub fn parse(selector: &str) -> Result<Self, SelectorError> {
+        Parser::new(selector).parse_selector_list()
+    }
+
+    /// Returns whether the given [`HtmlElement`] matches this selector.
+    ///
+    /// As the element is considered on its own, only the parts of the selector
+    /// that apply to the element itself are able to match.
+    pub fn matches(&self, element: &HtmlElement) -> bool {
+        self.matches_subject(&Subject::new(element, false, None))
+    }
+
+    fn matches_subject(&self, subject: &Subject) -> bool {
+        self.alternatives
+            .iter()
+            .any(|alternative| alternative.matches(subject))
+    }
+}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        Self::parse(selector)
+    }
+}
+
+/// A sequence of compound selectors separated by combinators (e.g., `ul > li a`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ComplexSelector {
+    /// The compound selectors, from left to right.
+    compounds: Vec<CompoundSelector>,
+
+    /// The combinators between the compound selectors, where `combinators[i]`
+    /// sits between `compounds[i]` and `compounds[i + 1]`.
+    combinators: Vec<Combinator>,
+}
+
+impl ComplexSelector {
+    fn matches(&self, subject: &Subject) -> bool {
+        let (last, rest) = self.compounds.split_last().expect("selector is not empty");
+
+        last.matches(subject) && self.matches_ancestors(rest, subject)
+    }
+
+    /// Returns whether the given compound selectors match the ancestors of the
+    /// given subject, according to the combinators between them.
+    fn matches_ancestors(&self, compounds: &[CompoundSelector], subject: &Subject) -> bool {
+        let Some((last, rest)) = compounds.split_last() else {
+            return true;
+        };
+
+        match self.combinators[compounds.len() - 1] {
+            Combinator::Child => subject
+                .parent
+                .is_some_and(|parent| last.matches(parent) && self.matches_ancestors(rest, parent)),
+            Combinator::Descendant => {
+                let mut ancestor = subject.parent;
+                while let Some(current) = ancestor {
+                    if last.matches(current) && self.matches_ancestors(rest, current) {
+                        return true;
+                    }
+
+                    ancestor = current.parent;
+                }
+
+                false
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    /// ` `
+    Descendant,
+    /// `>`
+    Child,
+}
+
+/// A sequence of simple selectors that all apply to the same element (e.g., `a.external[href]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct CompoundSelector {
+    /// The tag name, or `None` for the universal selector.
+    tag_name: Option<String>,
+    components: Vec<SimpleSelector>,
+}
+
+impl CompoundSelector {
+    fn matches(&self, subject: &Subject) -> bool {
+        let tag_matches = match &self.tag_name {
+            Some(tag_name) => tag_name.eq_ignore_ascii_case(subject.tag_name),
+            None => true,
+        };
+
+        tag_matches
+            && self
+                .components
+                .iter()
+                .all(|component| component.matches(subject))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SimpleSelector {
+    Class(String),
+    Id(String),
+    Attribute { name: String, value: Option<String> },
+    FirstChild,
+    Not(Box<CompoundSelector>),
+}
+
+impl SimpleSelector {
+    fn matches(&self, subject: &Subject) -> bool {
+        match self {
+            Self::Class(class) => subject
+                .attr("class")
+                .is_some_and(|classes| classes.split_ascii_whitespace().any(|name| name == class)),
+            Self::Id(id) => subject.attr("id") == Some(id),
+            Self::Attribute { name, value } => match (subject.attr(name), value) {
+                (Some(actual), Some(expected)) => actual == expected,
+                (Some(_), None) => true,
+                (None, _) => false,
+            },
+            Self::FirstChild => subject.is_first_child,
+            Self::Not(compound) => !compound.matches(subject),
+        }
+    }
+}
+
+/// An element being matched against a selector, along with the context
+/// needed to match combinators and structural pseudo-classes.
+struct Subject<'a> {
+    tag_name: &'a str,
+    attrs: &'a IndexMap<String, String>,
+    is_first_child: bool,
+    parent: Option<&'a Subject<'a>>,
+}
+
+impl<'a> Subject<'a> {
+    fn new(
+        element: &'a HtmlElement,
+        is_first_child: bool,
+        parent: Option<&'a Subject<'a>>,
+    ) -> Self {
+        Self {
+            tag_name: &element.tag_name,
+            attrs: &element.attrs,
+            is_first_child,
+            parent,
+        }
+    }
+
+    fn attr(&self, name: &str) -> Option<&'a str> {
+        self.attrs
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let char = self.peek()?;
+        self.position += char.len_utf8();
+        Some(char)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), SelectorError> {
+        match self.next() {
+            Some(char) if char == expected => Ok(()),
+            Some(char) => Err(self.unexpected(char)),
+            None => Err(SelectorError::UnexpectedEnd),
+        }
+    }
+
+    /// Returns an error for the given character, which was just consumed.
+    fn unexpected(&self, char: char) -> SelectorError {
+        SelectorError::UnexpectedChar(char, self.position - char.len_utf8())
+    }
+
+    /// Skips any whitespace, returning whether any was skipped.
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.position;
+        while self.peek().is_some_and(|char| char.is_ascii_whitespace()) {
+            self.next();
+        }
+
+        self.position > start
+    }
+
+    fn parse_selector_list(&mut self) -> Result<Selector, SelectorError> {
+        let mut alternatives = vec![self.parse_complex()?];
+
+        while self.peek() == Some(',') {
+            self.next();
+            alternatives.push(self.parse_complex()?);
+        }
+
+        match self.next() {
+            Some(char) => Err(self.unexpected(char)),
+            None => Ok(Selector { alternatives }),
+        }
+    }
+
+    fn parse_complex(&mut self) -> Result<ComplexSelector, SelectorError> {
+        self.skip_whitespace();
+
+        let mut compounds = vec![self.parse_compound()?];
+        let mut combinators = Vec::new();
+
+        loop {
+            let had_whitespace = self.skip_whitespace();
+
+            let combinator = match self.peek() {
+                Some('>') => {
+                    self.next();
+                    self.skip_whitespace();
+                    Combinator::Child
+                }
+                Some(',') | None => break,
+                Some(_) if had_whitespace => Combinator::Descendant,
+                Some(_) => break,
+            };
+
+            combinators.push(combinator);
+            compounds.push(self.parse_compound()?);
+        }
+
+        Ok(ComplexSelector {
+            compounds,
+            combinators,
+        })
+    }
+
+    fn parse_compound(&mut self) -> Result<CompoundSelector, SelectorError> {
+        let mut compound = CompoundSelector::default();
+        let mut is_empty = true;
+
+        match self.peek() {
+            Some('*') => {
+                self.next();
+                is_empty = false;
+            }
+            Some(char) if is_ident_char(char) => {
+                compound.tag_name = Some(self.parse_ident()?);
+                is_empty = false;
+            }
+            _ => {}
+        }
+
+        loop {
+            let component = match self.peek() {
+                Some('.') => {
+                    self.next();
+                    SimpleSelector::Class(self.parse_ident()?)
+                }
+                Some('#') => {
+                    self.next();
+                    SimpleSelector::Id(self.parse_ident()?)
+                }
+                Some('[') => {
+                    self.next();
+                    self.parse_attribute()?
+                }
+                Some(':') => {
+                    self.next();
+                    self.parse_pseudo_class()?
+                }
+                _ => break,
+            };
+
+            compound.components.push(component);
+            is_empty = false;
+        }
+
+        if is_empty {
+            return match self.next() {
+                Some(char) => Err(self.unexpected(char)),
+                None => Err(SelectorError::UnexpectedEnd),
+            };
+        }
+
+        Ok(compound)
+    }
+
+    fn parse_attribute(&mut self) -> Result<SimpleSelector, SelectorError> {
+        self.skip_whitespace();
+        let name = self.parse_ident()?;
+        self.skip_whitespace();
+
+        let value = match self.next() {
+            Some(']') => return Ok(SimpleSelector::Attribute { name, value: None }),
+            Some('=') => {
+                self.skip_whitespace();
+                let value = match self.peek() {
+                    Some(quote @ ('"' | '\'')) => {
+                        self.next();
+                        let start = self.position;
+                        let length = self.input[start..]
+                            .find(quote)
+                            .ok_or(SelectorError::UnexpectedEnd)?;
+                        self.position += length;
+                        let value = self.input[start..self.position].to_string();
+                        self.next();
+                        value
+                    }
+                    _ => self.parse_ident()?,
+                };
+                self.skip_whitespace();
+                value
+            }
+            Some(char) => return Err(self.unexpected(char)),
+            None => return Err(SelectorError::UnexpectedEnd),
+        };
+
+        self.expect(']')?;
+
+        Ok(SimpleSelector::Attribute {
+            name,
+            value: Some(value),
+        })
+    }
+
+    fn parse_pseudo_class(&mut self) -> Result<SimpleSelector, SelectorError> {
+        let name = self.parse_ident()?;
+
+        match name.to_ascii_lowercase().as_str() {
+            "first-child" => Ok(SimpleSelector::FirstChild),
+            "not" => {
+                self.expect('(')?;
+                self.skip_whitespace();
+                let compound = self.parse_compound()?;
+                self.skip_whitespace();
+                self.expect(')')?;
+
+                Ok(SimpleSelector::Not(Box::new(compound)))
+            }
+            _ => Err(SelectorError::UnsupportedPseudoClass(name)),
+        }
+    }
+
+    fn parse_ident(&mut self) -> Result<String, SelectorError> {
+        let start = self.position;
+        while self.peek().is_some_and(is_ident_char) {
+            self.next();
+        }
+
+        if self.position == start {
+            return match self.next() {
+                Some(char) => Err(self.unexpected(char)),
+                None => Err(SelectorError::UnexpectedEnd),
+            };
+        }
+
+        Ok(self.input[start..self.position].to_string())
+    }
+}
+
+fn is_ident_char(char: char) -> bool {
+    char.is_ascii_alphanumeric() || matches!(char, '-' | '_') || !char.is_ascii()
+}
+
+/// A trait for querying trees of [`HtmlElement`]s using a [`Selector`].
+///
+/// Only descendants are matched: when selecting from an [`HtmlElement`] the
+/// element itself is never matched, and when selecting from a list of
+/// [`Element`]s each element in the list and its descendants are matched.
+/// Matches are returned in document order.
+pub trait Select {
+    /// Returns all of the elements that match the given [`Selector`].
+    fn select(&self, selector: &Selector) -> Vec<&HtmlElement>;
+
+    /// Returns the first element that matches the given [`Selector`].
+    fn select_first(&self, selector: &Selector) -> Option<&HtmlElement> {
+        self.select(selector).into_iter().next()
+    }
+
+    /// Calls the given function with each element that matches the given
+    /// [`Selector`], in document order.
+    ///
+    /// An element is matched before the function is called with it, and its
+    /// descendants are matched afterwards, so changes made to an element are
+    /// reflected when matching its descendants.
+    fn select_mut(&mut self, selector: &Selector, f: impl FnMut(&mut HtmlElement));
+}
+
+impl Select for HtmlElement {
+    fn select(&self, selector: &Selector) -> Vec<&HtmlElement> {
+        let subject = Subject::new(self, false, None);
+
+        let mut matches = Vec::new();
+        select_children(&self.children, selector, Some(&subject), &mut matches);
+        matches
+    }
+
+    fn select_mut(&mut self, selector: &Selector, mut f: impl FnMut(&mut HtmlElement)) {
+        let HtmlElement {
+            tag_name,
+            attrs,
+            children,
+        } = self;
+        let subject = Subject {
+            tag_name,
+            attrs,
+            is_first_child: false,
+            parent: None,
+        };
+
+        select_children_mut(children, selector, Some(&subject), &mut f);
+    }
+}
+
+impl Select for [Element] {
+    fn select(&self, selector: &Selector) -> Vec<&HtmlElement> {
+        let mut matches = Vec::new();
+        select_children(self, selector, None, &mut matches);
+        matches
+    }
+
+    fn select_mut(&mut self, selector: &Selector, mut f: impl FnMut(&mut HtmlElement)) {
+        select_children_mut(self, selector, None, &mut f);
+    }
+}
+
+impl Select for Fragment {
+    fn select(&self, selector: &Selector) -> Vec<&HtmlElement> {
+        self.children.select(selector)
+    }
+
+    fn select_mut(&mut self, selector: &Selector, f: impl FnMut(&mut HtmlElement)) {
+        self.children.select_mut(selector, f)
+    }
+}
+
+/// Returns the index of the first [`HtmlElement`] in the given children.
+fn first_element_index(children: &[Element]) -> Option<usize> {
+    children
+        .iter()
+        .position(|child| matches!(child, Element::Html(_)))
+}
+
+fn select_children<'a>(
+    children: &'a [Element],
+    selector: &Selector,
+    parent: Option<&Subject>,
+    matches: &mut Vec<&'a HtmlElement>,
+) {
+    let first_element_index = first_element_index(children);
+
+    for (index, child) in children.iter().enumerate() {
+        let Element::Html(element) = child else {
+            continue;
+        };
+
+        let subject = Subject::new(element, first_element_index == Some(index), parent);
+        if selector.matches_subject(&subject) {
+            matches.push(element);
+        }
+
+        select_children(&element.children, selector, Some(&subject), matches);
+    }
+}
+
+fn select_children_mut(
+    children: &mut [Element],
+    selector: &Selector,
+    parent: Option<&Subject>,
+    f: &mut impl FnMut(&mut HtmlElement),
+) {
+    let first_element_index = first_element_index(children);
+
+    for (index, child) in children.iter_mut().enumerate() {
+        let Element::Html(element) = child else {
+            continue;
+        };
+
+        let is_first_child = first_element_index == Some(index);
+        if selector.matches_subject(&Subject::new(element, is_first_child, parent)) {
+            f(element);
+        }
+
+        let HtmlElement {
+            tag_name,
+            attrs,
+            children,
+        } = element;
+        let subject = Subject {
+            tag_name,
+            attrs,
+            is_first_child,
+            parent,
+        };
+
+        select_children_mut(children, selector, Some(&subject), f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_yaml_snapshot;
+
+    use crate::renderer::HtmlElementRenderer;
+    use crate::*;
+
+    use super::*;
+
+    fn page() -> HtmlElement {
+        body()
+            .child(
+                nav().id("nav").child(
+                    ul().child(li().class("item active").child(a().href("/").child("Home")))
+                        .child(li().class("item").child(a().href("/about").child("About")))
+                        .child(li().class("item").child(a().child("Contact"))),
+                ),
+            )
+            .child(
+                main()
+                    .child(h1().child("Title"))
+                    .child(p().class("lead").child("Lead"))
+                    .child(div().child(p().child("Nested"))),
+            )
+    }
+
+    fn select(element: &HtmlElement, selector: &str) -> Vec<String> {
+        element
+            .select(&Selector::parse(selector).unwrap())
+            .into_iter()
+            .map(|element| {
+                HtmlElementRenderer::new()
+                    .render_to_string(element)
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_select() {
+        let page = page();
+
+        assert_yaml_snapshot!([
+            "li",
+            ".active a",
+            "#nav > ul > li:first-child",
+            "a[href]",
+            "a[href=\"/about\"]",
+            "li:not(.active) > a",
+            "main > p",
+            "main p:not(.lead)",
+            "h1, .lead",
+            "body > li",
+        ]
+        .into_iter()
+        .map(|selector| (selector, select(&page, selector)))
+        .collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_select_first() {
+        let page = page();
+
+        let link = page
+            .select_first(&Selector::parse("li.item:not(:first-child) a").unwrap())
+            .unwrap();
+        assert_eq!(link.attrs.get("href"), Some(&"/about".to_string()));
+
+        assert!(page
+            .select_first(&Selector::parse("table").unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn test_select_elements() {
+        let elements = [
+            Element::from(p().class("first")),
+            Element::from("text"),
+            Element::from(p().child(span())),
+        ];
+
+        let selector = Selector::parse("p:first-child, p > span").unwrap();
+        let matches = elements
+            .select(&selector)
+            .into_iter()
+            .map(|element| element.tag_name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(matches, vec!["p", "span"]);
+    }
+
+    #[test]
+    fn test_select_mut() {
+        let mut page = page();
+
+        page.select_mut(&Selector::parse("a:not([href])").unwrap(), |element| {
+            element.attrs.insert("href".to_string(), "#".to_string());
+        });
+        page.select_mut(&Selector::parse("main p").unwrap(), |element| {
+            element
+                .attrs
+                .insert("class".to_string(), "text".to_string());
+        });
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new().render_to_string(&page).unwrap());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Selector::parse(""), Err(SelectorError::UnexpectedEnd));
+        assert_eq!(Selector::parse("p >"), Err(SelectorError::UnexpectedEnd));
+        assert_eq!(
+            Selector::parse("p + a"),
+            Err(SelectorError::UnexpectedChar('+', 2))
+        );
+        assert_eq!(
+            Selector::parse("a:hover"),
+            Err(SelectorError::UnsupportedPseudoClass("hover".to_string()))
+        );
+        assert_eq!(Selector::parse("[href"), Err(SelectorError::UnexpectedEnd));
+    }
+}
@@ -0,0 +1,377 @@
+//! Constructs for building inline styles.
+
+use std::error::Error;
+use std::fmt;
+
+use indexmap::IndexMap;
+
+use crate::AttrValue;
+
+/// An error that occurred while building a [`Style`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StyleError {
+    /// A declaration has a property name that is not a valid CSS property name.
+    InvalidPropertyName(String),
+}
+
+impl fmt::Display for StyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPropertyName(name) => write!(f, "invalid CSS property name: {name:?}"),
+        }
+    }
+}
+
+impl Error for StyleError {}
+
+/// Returns whether the given CSS property name is valid.
+///
+/// A valid property name is either a [custom property](https://developer.mozilla.org/en-US/docs/Web/CSS/--*)
+/// (e.g., `--spacing-4`), or consists of ASCII letters, digits, and hyphens,
+/// starting with a letter or a vendor prefix (e.g., `-webkit-`).
+pub fn is_valid_property_name(name: &str) -> bool {
+    if let Some(custom) = name.strip_prefix("--") {
+        return !custom.is_empty()
+            && custom.chars().all(|char| {
+                char.is_ascii_alphanumeric() || matches!(char, '-' | '_') || !char.is_ascii()
+            });
+    }
+
+    let name = name.strip_prefix('-').unwrap_or(name);
+
+    name.starts_with(|char: char| char.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '-')
+}
+
+/// A set of CSS declarations for the `style` attribute of an element.
+///
+/// Declarations are rendered in the order their properties were first set,
+/// and setting a property again replaces its value. Values are escaped so that
+/// they cannot end their declaration early or affect the declarations that
+/// follow them.
+///
+/// ```
+/// use auk::*;
+/// use auk::style::Style;
+///
+/// let base = Style::new()
+///     .set("display", "flex")
+///     .set("--gap", "var(--spacing-4)");
+///
+/// let element = div()
+///     .add_style(base)
+///     .add_style(Style::new().set("display", "grid").set("color", "red"));
+///
+/// assert_eq!(
+///     element.attrs.get("style").unwrap(),
+///     "display: grid; --gap: var(--spacing-4); color: red"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Style {
+    declarations: IndexMap<String, String>,
+}
+
+impl Style {
+    /// Returns a new, empty [`Style`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a [`Style`] with the declarations in the given CSS (e.g., the
+    /// value of an existing `style` attribute).
+    ///
+    /// Declarations that are malformed or have invalid property names are skipped.
+    pub fn from_css(css: &str) -> Self {
+        let mut style = Self::new();
+
+        for declaration in split_declarations(css) {
+            let Some((property, value)) = declaration.split_once(':') else {
+                continue;
+            };
+
+            let (property, value) = (property.trim(), value.trim());
+            if is_valid_property_name(property) && !value.is_empty() {
+                style
+                    .declarations
+                    .insert(property.to_string(), value.to_string());
+            }
+        }
+
+        style
+    }
+
+    /// Sets the given property to the given value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the property name is not valid. Use [`Style::try_set`] for
+    /// property names that are not known ahead of time.
+    pub fn set(self, property: impl Into<String>, value: impl Into<String>) -> Self {
+        match self.try_set(property, value) {
+            Ok(style) => style,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Sets the given property to the given value, returning an error if the
+    /// property name is not valid.
+    pub fn try_set(
+        mut self,
+        property: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Result<Self, StyleError> {
+        let property = property.into();
+        if !is_valid_property_name(&property) {
+            return Err(StyleError::InvalidPropertyName(property));
+        }
+
+        self.declarations
+            .insert(property, escape_value(&value.into()));
+        Ok(self)
+    }
+
+    /// Removes the given property.
+    pub fn remove(mut self, property: &str) -> Self {
+        self.declarations.shift_remove(property);
+        self
+    }
+
+    /// Merges the declarations from the given [`Style`] into this one.
+    ///
+    /// Where both set the same property, the value from `other` is used.
+    pub fn merge(mut self, other: Style) -> Self {
+        self.declarations.extend(other.declarations);
+        self
+    }
+
+    /// Returns the value of the given property.
+    pub fn get(&self, property: &str) -> Option<&str> {
+        self.declarations.get(property).map(String::as_str)
+    }
+
+    /// Returns whether this [`Style`] has no declarations.
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty()
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (property, value)) in self.declarations.iter().enumerate() {
+            if index > 0 {
+                write!(f, "; ")?;
+            }
+
+            write!(f, "{property}: {value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Style> for String {
+    fn from(value: Style) -> Self {
+        value.to_string()
+    }
+}
+
+impl AttrValue for Style {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+/// Splits the given CSS into declarations on the `;`s that are not inside of
+/// a string, parentheses, or escaped.
+fn split_declarations(css: &str) -> Vec<&str> {
+    let mut declarations = Vec::new();
+    let mut start = 0;
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut chars = css.char_indices();
+
+    while let Some((index, char)) = chars.next() {
+        match (char, quote) {
+            ('\\', _) => {
+                chars.next();
+            }
+            ('"' | '\'', None) => quote = Some(char),
+            (char, Some(open)) if char == open => quote = None,
+            (_, Some(_)) => {}
+            ('(', None) => depth += 1,
+            (')', None) => depth = depth.saturating_sub(1),
+            (';', None) if depth == 0 => {
+                declarations.push(&css[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    declarations.push(&css[start..]);
+    declarations
+}
+
+/// Escapes the given value so that it stays within its declaration.
+///
+/// Outside of strings, the characters that would end the declaration or
+/// start a block (`;`, `{`, and `}`) are escaped, as is the start of a
+/// comment. Unterminated strings, functions, and brackets are closed, and line
+/// breaks are replaced with spaces. Existing escapes (e.g., `\2014`) are left
+/// as-is.
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let mut quote = None;
+    let mut closing = Vec::new();
+    let mut chars = value
+        .chars()
+        .map(|char| if char.is_control() { ' ' } else { char })
+        .peekable();
+
+    while let Some(char) = chars.next() {
+        match (char, quote) {
+            ('\\', _) => {
+                escaped.push('\\');
+                escaped.push(chars.next().unwrap_or('\\'));
+            }
+            ('"' | '\'', None) => {
+                quote = Some(char);
+                escaped.push(char);
+            }
+            (char, Some(open)) if char == open => {
+                quote = None;
+                escaped.push(char);
+            }
+            ('(', None) => {
+                closing.push(')');
+                escaped.push(char);
+            }
+            ('[', None) => {
+                closing.push(']');
+                escaped.push(char);
+            }
+            (')' | ']', None) if closing.last() == Some(&char) => {
+                closing.pop();
+                escaped.push(char);
+            }
+            (';' | '{' | '}', None) => {
+                escaped.push('\\');
+                escaped.push(char);
+            }
+            ('/', None) if chars.peek() == Some(&'*') => escaped.push_str("\\/"),
+            (char, _) => escaped.push(char),
+        }
+    }
+
+    if let Some(quote) = quote {
+        escaped.push(quote);
+    }
+
+    escaped.extend(closing.into_iter().rev());
+
+    escaped.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    use super::*;
+
+    #[test]
+    fn test_property_names() {
+        for name in [
+            "color",
+            "background-color",
+            "-webkit-line-clamp",
+            "--spacing-4",
+            "--_x",
+            "--ÿ",
+        ] {
+            assert!(
+                is_valid_property_name(name),
+                "expected {name:?} to be valid"
+            );
+        }
+
+        for name in [
+            "",
+            "-",
+            "--",
+            "4px",
+            "color:",
+            "color;",
+            "back ground",
+            "--a b",
+            "-1a",
+        ] {
+            assert!(
+                !is_valid_property_name(name),
+                "expected {name:?} to be invalid"
+            );
+        }
+
+        assert_eq!(
+            Style::new().try_set("color: red; x", "blue"),
+            Err(StyleError::InvalidPropertyName("color: red; x".to_string()))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid CSS property name")]
+    fn test_set_panics_on_invalid_property_names() {
+        let _ = Style::new().set("color;", "red");
+    }
+
+    #[test]
+    fn test_values_are_escaped() {
+        let style = Style::new()
+            .set("color", "red; background: url(javascript:alert(1))")
+            .set("font-family", "\"Fira Sans\", sans-serif")
+            .set("content", "\"a; b\" '\\2014'")
+            .set("--unterminated", "\"oops; color: blue")
+            .set("margin", "0 /* comment */ auto")
+            .set("padding", "1px}\n.x{")
+            .set("width", "calc(100% - var(--gap; color: blue");
+
+        assert_eq!(
+            style.to_string(),
+            r#"color: red\; background: url(javascript:alert(1)); font-family: "Fira Sans", sans-serif; content: "a; b" '\2014'; --unterminated: "oops; color: blue"; margin: 0 \/* comment */ auto; padding: 1px\} .x\{; width: calc(100% - var(--gap\; color: blue))"#
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let style = Style::new()
+            .set("display", "flex")
+            .set("gap", "var(--spacing-2)")
+            .merge(
+                Style::new()
+                    .set("gap", "var(--spacing-4)")
+                    .set("color", "red"),
+            )
+            .remove("display");
+
+        assert_eq!(style.to_string(), "gap: var(--spacing-4); color: red");
+        assert_eq!(style.get("color"), Some("red"));
+    }
+
+    #[test]
+    fn test_add_style_merges_with_existing_style() {
+        let element = div()
+            .style("color: blue; background: url(\"a;b.png\"); invalid; 1x: y")
+            .add_style(Style::new().set("color", "red").set("--spacing-4", "1rem"));
+
+        assert_eq!(
+            element.attrs.get("style").unwrap(),
+            r#"color: red; background: url("a;b.png"); --spacing-4: 1rem"#
+        );
+
+        let element = element.add_style(Style::new());
+        assert!(element.attrs.contains_key("style"));
+        assert!(!div().add_style(Style::new()).attrs.contains_key("style"));
+    }
+}
@@ -0,0 +1,203 @@
+//! Constructors for [SVG](https://developer.mozilla.org/en-US/docs/Web/SVG) elements.
+//!
+//! SVG element and attribute names are case-sensitive, so these constructors
+//! and the methods on [`SvgAttributes`] take care of using the correct casing
+//! (e.g., [`linear_gradient`] creates a `<linearGradient>` and
+//! [`SvgAttributes::view_box`] sets the `viewBox` attribute).
+//!
+//! ```
+//! use auk::WithChildren;
+//! use auk::renderer::HtmlElementRenderer;
+//! use auk::svg::{self, SvgAttributes};
+//!
+//! let icon = svg::svg()
+//!     .view_box("0 0 24 24")
+//!     .child(svg::circle().cx("12").cy("12").r("10").stroke_width("2"));
+//!
+//! assert_eq!(
+//!     HtmlElementRenderer::new().render_to_string(&icon).unwrap(),
+//!     r#"<svg viewBox="0 0 24 24"><circle cx="12" cy="12" r="10" stroke-width="2"/></svg>"#
+//! );
+//! ```
+
+use crate::{AttrValue, HtmlElement};
+
+macro_rules! svg_elements {
+    ($($name:ident => $tag_name:literal),* $(,)?) => {
+        $(
+            #[doc = concat!("[`<", $tag_name, ">`](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/", $tag_name, ")")]
+            pub fn $name() -> HtmlElement {
+                HtmlElement::new($tag_name)
+            }
+        )*
+    }
+}
+
+svg_elements!(
+    a => "a",
+    animate => "animate",
+    animate_motion => "animateMotion",
+    animate_transform => "animateTransform",
+    circle => "circle",
+    clip_path => "clipPath",
+    defs => "defs",
+    desc => "desc",
+    ellipse => "ellipse",
+    fe_blend => "feBlend",
+    fe_color_matrix => "feColorMatrix",
+    fe_component_transfer => "feComponentTransfer",
+    fe_composite => "feComposite",
+    fe_convolve_matrix => "feConvolveMatrix",
+    fe_diffuse_lighting => "feDiffuseLighting",
+    fe_displacement_map => "feDisplacementMap",
+    fe_distant_light => "feDistantLight",
+    fe_drop_shadow => "feDropShadow",
+    fe_flood => "feFlood",
+    fe_func_a => "feFuncA",
+    fe_func_b => "feFuncB",
+    fe_func_g => "feFuncG",
+    fe_func_r => "feFuncR",
+    fe_gaussian_blur => "feGaussianBlur",
+    fe_image => "feImage",
+    fe_merge => "feMerge",
+    fe_merge_node => "feMergeNode",
+    fe_morphology => "feMorphology",
+    fe_offset => "feOffset",
+    fe_point_light => "fePointLight",
+    fe_specular_lighting => "feSpecularLighting",
+    fe_spot_light => "feSpotLight",
+    fe_tile => "feTile",
+    fe_turbulence => "feTurbulence",
+    filter => "filter",
+    foreign_object => "foreignObject",
+    g => "g",
+    image => "image",
+    line => "line",
+    linear_gradient => "linearGradient",
+    marker => "marker",
+    mask => "mask",
+    metadata => "metadata",
+    mpath => "mpath",
+    path => "path",
+    pattern => "pattern",
+    polygon => "polygon",
+    polyline => "polyline",
+    radial_gradient => "radialGradient",
+    rect => "rect",
+    script => "script",
+    set => "set",
+    stop => "stop",
+    style => "style",
+    svg => "svg",
+    switch => "switch",
+    symbol => "symbol",
+    text => "text",
+    text_path => "textPath",
+    title => "title",
+    tspan => "tspan",
+    use_ => "use",
+    view => "view",
+);
+
+macro_rules! svg_attributes {
+    ($($name:ident => $attr_name:literal),* $(,)?) => {
+        /// Methods for setting [SVG attributes](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute)
+        /// on an [`HtmlElement`].
+        pub trait SvgAttributes: Sized {
+            $(
+                #[doc = concat!("Sets the `", $attr_name, "` attribute to the provided value.")]
+                fn $name(self, value: impl AttrValue) -> Self;
+            )*
+        }
+
+        impl SvgAttributes for HtmlElement {
+            $(
+                fn $name(self, value: impl AttrValue) -> Self {
+                    self.attr($attr_name, value)
+                }
+            )*
+        }
+    }
+}
+
+svg_attributes!(
+    attribute_name => "attributeName",
+    begin => "begin",
+    calc_mode => "calcMode",
+    clip_path_units => "clipPathUnits",
+    clip_rule => "clip-rule",
+    color => "color",
+    cx => "cx",
+    cy => "cy",
+    d => "d",
+    dominant_baseline => "dominant-baseline",
+    dur => "dur",
+    dx => "dx",
+    dy => "dy",
+    fill => "fill",
+    fill_opacity => "fill-opacity",
+    fill_rule => "fill-rule",
+    font_family => "font-family",
+    font_size => "font-size",
+    font_weight => "font-weight",
+    from => "from",
+    fx => "fx",
+    fy => "fy",
+    gradient_transform => "gradientTransform",
+    gradient_units => "gradientUnits",
+    in_ => "in",
+    in2 => "in2",
+    key_times => "keyTimes",
+    length_adjust => "lengthAdjust",
+    marker_end => "marker-end",
+    marker_height => "markerHeight",
+    marker_mid => "marker-mid",
+    marker_start => "marker-start",
+    marker_units => "markerUnits",
+    marker_width => "markerWidth",
+    mask_units => "maskUnits",
+    offset => "offset",
+    opacity => "opacity",
+    orient => "orient",
+    path_length => "pathLength",
+    pattern_content_units => "patternContentUnits",
+    pattern_transform => "patternTransform",
+    pattern_units => "patternUnits",
+    points => "points",
+    preserve_aspect_ratio => "preserveAspectRatio",
+    r => "r",
+    ref_x => "refX",
+    ref_y => "refY",
+    repeat_count => "repeatCount",
+    result => "result",
+    rx => "rx",
+    ry => "ry",
+    spread_method => "spreadMethod",
+    std_deviation => "stdDeviation",
+    stop_color => "stop-color",
+    stop_opacity => "stop-opacity",
+    stroke => "stroke",
+    stroke_dasharray => "stroke-dasharray",
+    stroke_dashoffset => "stroke-dashoffset",
+    stroke_linecap => "stroke-linecap",
+    stroke_linejoin => "stroke-linejoin",
+    stroke_miterlimit => "stroke-miterlimit",
+    stroke_opacity => "stroke-opacity",
+    stroke_width => "stroke-width",
+    text_anchor => "text-anchor",
+    text_length => "textLength",
+    to => "to",
+    transform => "transform",
+    values => "values",
+    vector_effect => "vector-effect",
+    view_box => "viewBox",
+    visibility => "visibility",
+    x => "x",
+    x1 => "x1",
+    x2 => "x2",
+    xlink_href => "xlink:href",
+    xmlns => "xmlns",
+    y => "y",
+    y1 => "y1",
+    y2 => "y2",
+);
@@ -0,0 +1,230 @@
+//! Constructs for restricting the URLs that can appear in rendered HTML.
+
+use std::collections::HashSet;
+
+/// The value that disallowed URLs are replaced with when they are neutralized.
+pub const NEUTRALIZED_URL: &str = "about:invalid";
+
+/// What to do when a URL is not allowed by a [`UrlPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisallowedUrlAction {
+    /// Replace the URL with [`NEUTRALIZED_URL`].
+    #[default]
+    Neutralize,
+
+    /// Reject the URL with an error.
+    Reject,
+}
+
+/// A policy for which URLs are allowed to appear in URL-bearing attributes
+/// (e.g., `href` or `src`).
+///
+/// The default policy allows the `http`, `https`, `mailto`, and `tel` schemes,
+/// as well as relative URLs, and neutralizes everything else (most notably
+/// `javascript:` and `data:` URLs).
+#[derive(Debug, Clone)]
+pub struct UrlPolicy {
+    allowed_schemes: HashSet<String>,
+    allow_relative: bool,
+    url_attributes: HashSet<String>,
+    on_disallowed: DisallowedUrlAction,
+}
+
+impl Default for UrlPolicy {
+    fn default() -> Self {
+        Self::new()
+            .allow_schemes(["http", "https", "mailto", "tel"])
+            .url_attributes([
+                "action",
+                "background",
+                "cite",
+                "data",
+                "formaction",
+                "href",
+                "icon",
+                "imagesrcset",
+                "longdesc",
+                "manifest",
+                "ping",
+                "poster",
+                "src",
+                "srcset",
+                "xlink:href",
+            ])
+    }
+}
+
+impl UrlPolicy {
+    /// Returns a new [`UrlPolicy`] that allows only relative URLs and has no
+    /// URL-bearing attributes.
+    ///
+    /// Use [`UrlPolicy::default`] for a policy with sensible defaults.
+    pub fn new() -> Self {
+        Self {
+            allowed_schemes: HashSet::new(),
+            allow_relative: true,
+            url_attributes: HashSet::new(),
+            on_disallowed: DisallowedUrlAction::default(),
+        }
+    }
+
+    /// Allows URLs with the given scheme (e.g., `https`).
+    pub fn allow_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.allowed_schemes
+            .insert(scheme.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Allows URLs with any of the given schemes.
+    pub fn allow_schemes(self, schemes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        schemes.into_iter().fold(self, Self::allow_scheme)
+    }
+
+    /// Sets whether relative URLs (those without a scheme) are allowed.
+    pub fn allow_relative(mut self, allow_relative: bool) -> Self {
+        self.allow_relative = allow_relative;
+        self
+    }
+
+    /// Marks the attribute with the given name as containing URLs.
+    pub fn url_attribute(mut self, name: impl Into<String>) -> Self {
+        self.url_attributes.insert(name.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Marks the attributes with the given names as containing URLs.
+    pub fn url_attributes(self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        names.into_iter().fold(self, Self::url_attribute)
+    }
+
+    /// Sets what to do with URLs that are not allowed by this policy.
+    pub fn on_disallowed(mut self, action: DisallowedUrlAction) -> Self {
+        self.on_disallowed = action;
+        self
+    }
+
+    /// Returns what to do with URLs that are not allowed by this policy.
+    pub fn disallowed_action(&self) -> DisallowedUrlAction {
+        self.on_disallowed
+    }
+
+    /// Returns whether the attribute with the given name contains URLs.
+    pub fn is_url_attribute(&self, name: &str) -> bool {
+        self.url_attributes.contains(&name.to_ascii_lowercase())
+    }
+
+    /// Returns whether the given URL is allowed by this policy.
+    pub fn is_allowed(&self, url: &str) -> bool {
+        match scheme(url) {
+            Some(scheme) => self.allowed_schemes.contains(&scheme.to_ascii_lowercase()),
+            None => self.allow_relative,
+        }
+    }
+
+    /// Returns whether the value of the attribute with the given name is
+    /// allowed by this policy.
+    ///
+    /// Attributes that are not URL-bearing are always allowed. Attributes that
+    /// contain multiple URLs (`srcset`, `imagesrcset`, and `ping`) are only
+    /// allowed if every URL in them is.
+    pub fn is_attribute_allowed(&self, name: &str, value: &str) -> bool {
+        if !self.is_url_attribute(name) {
+            return true;
+        }
+
+        match name.to_ascii_lowercase().as_str() {
+            "srcset" | "imagesrcset" => value
+                .split(',')
+                .filter_map(|candidate| candidate.split_ascii_whitespace().next())
+                .all(|url| self.is_allowed(url)),
+            "ping" => value
+                .split_ascii_whitespace()
+                .all(|url| self.is_allowed(url)),
+            _ => self.is_allowed(value),
+        }
+    }
+}
+
+/// Returns the scheme of the given URL, or `None` if it is a relative URL.
+///
+/// Leading and trailing whitespace and control characters are ignored, as are
+/// tabs and newlines anywhere within the URL, as browsers do the same when
+/// parsing URLs (e.g., `java\nscript:` is treated as `javascript:`).
+pub fn scheme(url: &str) -> Option<String> {
+    let url = url.trim_matches(|char: char| char <= ' ');
+
+    let mut scheme = String::new();
+    for char in url
+        .chars()
+        .filter(|char| !matches!(char, '\t' | '\n' | '\r'))
+    {
+        match char {
+            ':' => break,
+            'a'..='z' | 'A'..='Z' => scheme.push(char),
+            '0'..='9' | '+' | '-' | '.' if !scheme.is_empty() => scheme.push(char),
+            _ => return None,
+        }
+    }
+
+    let has_colon = url.chars().any(|char| char == ':');
+
+    Some(scheme).filter(|scheme| has_colon && !scheme.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheme() {
+        assert_eq!(scheme("https://example.com"), Some("https".to_string()));
+        assert_eq!(
+            scheme("  JavaScript:alert(1)"),
+            Some("JavaScript".to_string())
+        );
+        assert_eq!(
+            scheme("java\tscr\nipt:alert(1)"),
+            Some("javascript".to_string())
+        );
+        assert_eq!(
+            scheme("\u{0}javascript:alert(1)"),
+            Some("javascript".to_string())
+        );
+        assert_eq!(scheme("/path/to:file"), None);
+        assert_eq!(scheme("path?query=a:b"), None);
+        assert_eq!(scheme("#fragment"), None);
+        assert_eq!(scheme("relative"), None);
+        assert_eq!(scheme(""), None);
+    }
+
+    #[test]
+    fn test_default_policy() {
+        let policy = UrlPolicy::default();
+
+        assert!(policy.is_attribute_allowed("href", "https://example.com"));
+        assert!(policy.is_attribute_allowed("href", "mailto:auk@example.com"));
+        assert!(policy.is_attribute_allowed("href", "/about"));
+        assert!(policy.is_attribute_allowed("HREF", "#top"));
+        assert!(!policy.is_attribute_allowed("href", "javascript:alert(1)"));
+        assert!(!policy.is_attribute_allowed("src", "data:text/html,<script>alert(1)</script>"));
+        assert!(!policy.is_attribute_allowed("formaction", "JAVASCRIPT:alert(1)"));
+        assert!(policy.is_attribute_allowed("title", "javascript:alert(1)"));
+
+        assert!(policy.is_attribute_allowed("srcset", "a.png 1x, https://example.com/b.png 2x"));
+        assert!(!policy.is_attribute_allowed("srcset", "a.png 1x, javascript:alert(1) 2x"));
+        assert!(!policy.is_attribute_allowed("ping", "/track javascript:alert(1)"));
+    }
+
+    #[test]
+    fn test_custom_policy() {
+        let policy = UrlPolicy::new()
+            .allow_scheme("https")
+            .allow_relative(false)
+            .url_attribute("data-href");
+
+        assert!(policy.is_attribute_allowed("data-href", "https://example.com"));
+        assert!(!policy.is_attribute_allowed("data-href", "http://example.com"));
+        assert!(!policy.is_attribute_allowed("data-href", "/about"));
+        assert!(policy.is_attribute_allowed("href", "javascript:alert(1)"));
+    }
+}
@@ -1,17 +1,95 @@
 //! Constructs for traversing and manipulating trees of [`HtmlElement`]s.
 
-use crate::{Element, HtmlElement, TextElement};
+use crate::{
+    CDataElement, CommentElement, DoctypeElement, Element, Fragment, HtmlElement,
+    ProcessingInstructionElement, TextElement,
+};
+
+/// The context of the node currently being visited.
+///
+/// A visitor that returns a [`VisitContext`] from `context` will have it kept
+/// up to date as elements are entered and left during the traversal.
+///
+/// The context is updated by [`walk_element`] and [`noop_visit_element`], so
+/// it only reflects the elements that are walked by them.
+#[derive(Debug, Clone, Default)]
+pub struct VisitContext {
+    path: Vec<String>,
+}
+
+impl VisitContext {
+    /// Returns a new [`VisitContext`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the depth of the current node, where `0` is a root node.
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Returns the tag name of the parent element of the current node.
+    pub fn parent(&self) -> Option<&str> {
+        self.path.last().map(String::as_str)
+    }
+
+    /// Returns the tag names of the ancestors of the current node, starting
+    /// with the outermost one.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// Returns whether the current node is inside of an element with the
+    /// given tag name.
+    pub fn is_inside(&self, tag_name: &str) -> bool {
+        self.path.iter().any(|ancestor| ancestor == tag_name)
+    }
+
+    fn enter(&mut self, element: &HtmlElement) {
+        self.path.push(element.tag_name.clone());
+    }
+
+    fn leave(&mut self) {
+        self.path.pop();
+    }
+}
 
 /// A visitor for [`HtmlElement`]s.
 pub trait Visitor: Sized {
     /// The type of error this visitor returns.
     type Error;
 
+    /// Returns the [`VisitContext`] for this visitor, if it tracks one.
+    fn context(&mut self) -> Option<&mut VisitContext> {
+        None
+    }
+
     /// Visits the given [`HtmlElement`].
+    ///
+    /// The default implementation calls [`walk_element`], which is
+    /// responsible for calling `enter` and `leave` and for updating the
+    /// [`VisitContext`]. An override that does not call it skips all of these
+    /// for the element's subtree.
     fn visit(&mut self, element: &HtmlElement) -> Result<(), Self::Error> {
         walk_element(self, element)
     }
 
+    /// Called when the given [`HtmlElement`] is entered, before its attributes
+    /// and children are visited.
+    ///
+    /// This is called by [`walk_element`], not by `visit_children`.
+    fn enter(&mut self, _element: &HtmlElement) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called when the given [`HtmlElement`] is left, after its attributes and
+    /// children have been visited.
+    ///
+    /// This is called by [`walk_element`], not by `visit_children`.
+    fn leave(&mut self, _element: &HtmlElement) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Visits the given text.
     fn visit_text(&mut self, _text: &str, _safe: bool) -> Result<(), Self::Error> {
         Ok(())
@@ -22,20 +100,59 @@ pub trait Visitor: Sized {
         Ok(())
     }
 
+    /// Visits the given comment.
+    fn visit_comment(&mut self, _text: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Visits the given document type declaration.
+    fn visit_doctype(&mut self, _doctype: &DoctypeElement) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Visits the given processing instruction.
+    fn visit_processing_instruction(
+        &mut self,
+        _target: &str,
+        _data: &str,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Visits the given CDATA section.
+    fn visit_cdata(&mut self, _text: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Visits the children of an [`HtmlElement`].
     fn visit_children(&mut self, children: &[Element]) -> Result<(), Self::Error> {
         walk_children(self, children)
     }
+
+    /// Visits the given [`Fragment`].
+    fn visit_fragment(&mut self, fragment: &Fragment) -> Result<(), Self::Error> {
+        self.visit_children(&fragment.children)
+    }
 }
 
 /// Walks the given [`HtmlElement`].
 pub fn walk_element<V: Visitor>(visitor: &mut V, element: &HtmlElement) -> Result<(), V::Error> {
+    visitor.enter(element)?;
+    if let Some(context) = visitor.context() {
+        context.enter(element);
+    }
+
     for (name, value) in &element.attrs {
         visitor.visit_attr(name, value)?;
     }
 
     visitor.visit_children(&element.children)?;
 
+    if let Some(context) = visitor.context() {
+        context.leave();
+    }
+    visitor.leave(element)?;
+
     Ok(())
 }
 
@@ -45,6 +162,12 @@ pub fn walk_children<V: Visitor>(visitor: &mut V, children: &[Element]) -> Resul
         match child {
             Element::Html(element) => visitor.visit(element)?,
             Element::Text(TextElement { text, safe }) => visitor.visit_text(text, *safe)?,
+            Element::Comment(CommentElement { text }) => visitor.visit_comment(text)?,
+            Element::Doctype(doctype) => visitor.visit_doctype(doctype)?,
+            Element::ProcessingInstruction(ProcessingInstructionElement { target, data }) => {
+                visitor.visit_processing_instruction(target, data)?
+            }
+            Element::CData(CDataElement { text }) => visitor.visit_cdata(text)?,
         }
     }
 
@@ -56,11 +179,37 @@ pub trait MutVisitor: Sized {
     /// The type of error this visitor returns.
     type Error;
 
+    /// Returns the [`VisitContext`] for this visitor, if it tracks one.
+    fn context(&mut self) -> Option<&mut VisitContext> {
+        None
+    }
+
     /// Visits the given [`HtmlElement`].
+    ///
+    /// The default implementation calls [`noop_visit_element`], which is
+    /// responsible for calling `enter` and `leave` and for updating the
+    /// [`VisitContext`]. An override that does not call it skips all of these
+    /// for the element's subtree.
     fn visit(&mut self, element: &mut HtmlElement) -> Result<(), Self::Error> {
         noop_visit_element(self, element)
     }
 
+    /// Called when the given [`HtmlElement`] is entered, before its attributes
+    /// and children are visited.
+    ///
+    /// This is called by [`noop_visit_element`], not by `visit_children`.
+    fn enter(&mut self, _element: &mut HtmlElement) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called when the given [`HtmlElement`] is left, after its attributes and
+    /// children have been visited.
+    ///
+    /// This is called by [`noop_visit_element`], not by `visit_children`.
+    fn leave(&mut self, _element: &mut HtmlElement) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Visits the given text.
     fn visit_text(&mut self, _text: &mut String, _safe: &mut bool) -> Result<(), Self::Error> {
         Ok(())
@@ -71,10 +220,42 @@ pub trait MutVisitor: Sized {
         Ok(())
     }
 
+    /// Visits the given comment.
+    fn visit_comment(&mut self, _text: &mut String) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Visits the given document type declaration.
+    fn visit_doctype(&mut self, _doctype: &mut DoctypeElement) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Visits the given processing instruction.
+    fn visit_processing_instruction(
+        &mut self,
+        _target: &mut String,
+        _data: &mut String,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Visits the given CDATA section.
+    fn visit_cdata(&mut self, _text: &mut String) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Visit the children of an [`HtmlElement`].
-    fn visit_children(&mut self, children: &mut [Element]) -> Result<(), Self::Error> {
+    ///
+    /// Since the children are passed as a [`Vec`], nodes may be added to or
+    /// removed from it before (or instead of) walking them.
+    fn visit_children(&mut self, children: &mut Vec<Element>) -> Result<(), Self::Error> {
         noop_visit_children(self, children)
     }
+
+    /// Visits the given [`Fragment`].
+    fn visit_fragment(&mut self, fragment: &mut Fragment) -> Result<(), Self::Error> {
+        self.visit_children(&mut fragment.children)
+    }
 }
 
 /// Walks the given [`HtmlElement`] without mutating it.
@@ -82,30 +263,160 @@ pub fn noop_visit_element<V: MutVisitor>(
     visitor: &mut V,
     element: &mut HtmlElement,
 ) -> Result<(), V::Error> {
+    visitor.enter(element)?;
+    if let Some(context) = visitor.context() {
+        context.enter(element);
+    }
+
     for (name, value) in &mut element.attrs {
         visitor.visit_attr(name, value)?;
     }
 
     visitor.visit_children(&mut element.children)?;
 
+    if let Some(context) = visitor.context() {
+        context.leave();
+    }
+    visitor.leave(element)?;
+
     Ok(())
 }
 
-/// Walks the given children without mutating it..
+/// Walks the given children without mutating them.
 pub fn noop_visit_children<V: MutVisitor>(
     visitor: &mut V,
-    children: &mut [Element],
+    children: &mut Vec<Element>,
 ) -> Result<(), V::Error> {
     for child in children {
         match child {
             Element::Html(element) => visitor.visit(element)?,
             Element::Text(TextElement { text, safe }) => visitor.visit_text(text, safe)?,
+            Element::Comment(CommentElement { text }) => visitor.visit_comment(text)?,
+            Element::Doctype(doctype) => visitor.visit_doctype(doctype)?,
+            Element::ProcessingInstruction(ProcessingInstructionElement { target, data }) => {
+                visitor.visit_processing_instruction(target, data)?
+            }
+            Element::CData(CDataElement { text }) => visitor.visit_cdata(text)?,
         }
     }
 
     Ok(())
 }
 
+/// A transformer for trees of [`HtmlElement`]s.
+///
+/// Unlike [`MutVisitor`], which edits nodes in place, a [`Fold`] takes
+/// ownership of each node and returns the nodes that should take its place.
+/// This means that a node may be removed (by returning no nodes), replaced,
+/// wrapped, or unwrapped (by returning its children).
+pub trait Fold: Sized {
+    /// The type of error this folder returns.
+    type Error;
+
+    /// Folds the given [`HtmlElement`].
+    fn fold(&mut self, element: HtmlElement) -> Result<Vec<Element>, Self::Error> {
+        Ok(vec![noop_fold_element(self, element)?.into()])
+    }
+
+    /// Folds the given text.
+    fn fold_text(&mut self, text: TextElement) -> Result<Vec<Element>, Self::Error> {
+        Ok(vec![text.into()])
+    }
+
+    /// Folds the given attribute.
+    ///
+    /// Returning `None` removes the attribute.
+    fn fold_attr(
+        &mut self,
+        name: String,
+        value: String,
+    ) -> Result<Option<(String, String)>, Self::Error> {
+        Ok(Some((name, value)))
+    }
+
+    /// Folds the given comment.
+    fn fold_comment(&mut self, comment: CommentElement) -> Result<Vec<Element>, Self::Error> {
+        Ok(vec![comment.into()])
+    }
+
+    /// Folds the given document type declaration.
+    fn fold_doctype(&mut self, doctype: DoctypeElement) -> Result<Vec<Element>, Self::Error> {
+        Ok(vec![doctype.into()])
+    }
+
+    /// Folds the given processing instruction.
+    fn fold_processing_instruction(
+        &mut self,
+        processing_instruction: ProcessingInstructionElement,
+    ) -> Result<Vec<Element>, Self::Error> {
+        Ok(vec![processing_instruction.into()])
+    }
+
+    /// Folds the given CDATA section.
+    fn fold_cdata(&mut self, cdata: CDataElement) -> Result<Vec<Element>, Self::Error> {
+        Ok(vec![cdata.into()])
+    }
+
+    /// Folds the children of an [`HtmlElement`].
+    fn fold_children(&mut self, children: Vec<Element>) -> Result<Vec<Element>, Self::Error> {
+        noop_fold_children(self, children)
+    }
+
+    /// Folds the given [`Fragment`].
+    fn fold_fragment(&mut self, fragment: Fragment) -> Result<Fragment, Self::Error> {
+        Ok(Fragment::from(self.fold_children(fragment.children)?))
+    }
+}
+
+/// Folds the attributes and children of the given [`HtmlElement`], returning
+/// the element itself.
+pub fn noop_fold_element<F: Fold>(
+    folder: &mut F,
+    element: HtmlElement,
+) -> Result<HtmlElement, F::Error> {
+    let HtmlElement {
+        tag_name,
+        attrs,
+        children,
+    } = element;
+
+    let mut folded = HtmlElement::new(tag_name);
+
+    for (name, value) in attrs {
+        if let Some((name, value)) = folder.fold_attr(name, value)? {
+            folded.attrs.insert(name, value);
+        }
+    }
+
+    folded.children = folder.fold_children(children)?;
+
+    Ok(folded)
+}
+
+/// Folds each of the given children, collecting the nodes that take their
+/// place.
+pub fn noop_fold_children<F: Fold>(
+    folder: &mut F,
+    children: Vec<Element>,
+) -> Result<Vec<Element>, F::Error> {
+    let mut folded = Vec::with_capacity(children.len());
+
+    for child in children {
+        folded.extend(match child {
+            Element::Html(element) => folder.fold(element)?,
+            Element::Text(text) => folder.fold_text(text)?,
+            Element::Comment(comment) => folder.fold_comment(comment)?,
+            Element::Doctype(doctype) => folder.fold_doctype(doctype)?,
+            Element::ProcessingInstruction(processing_instruction) => {
+                folder.fold_processing_instruction(processing_instruction)?
+            }
+            Element::CData(cdata) => folder.fold_cdata(cdata)?,
+        });
+    }
+
+    Ok(folded)
+}
+
 #[cfg(test)]
 mod tests {
     use insta::assert_yaml_snapshot;
@@ -147,4 +458,128 @@ mod tests {
             .render_to_string(&element)
             .unwrap());
     }
+
+    #[test]
+    fn test_visit_comments() {
+        struct CommentCollector(Vec<String>);
+
+        impl Visitor for CommentCollector {
+            type Error = ();
+
+            fn visit_comment(&mut self, text: &str) -> Result<(), Self::Error> {
+                self.0.push(text.to_string());
+                Ok(())
+            }
+        }
+
+        let element = div()
+            .child(CommentElement::new(" start "))
+            .child(p().child("Hello").child(CommentElement::new(" more ")))
+            .child(CommentElement::new(" end "));
+
+        let mut visitor = CommentCollector(Vec::new());
+        visitor.visit(&element).unwrap();
+
+        assert_eq!(visitor.0, vec![" start ", " more ", " end "]);
+    }
+
+    #[test]
+    fn test_fold_wrap_and_remove() {
+        struct Tidy;
+
+        impl Fold for Tidy {
+            type Error = ();
+
+            fn fold(&mut self, element: HtmlElement) -> Result<Vec<Element>, Self::Error> {
+                let element = noop_fold_element(self, element)?;
+
+                match element.tag_name.as_str() {
+                    "p" if element.children.is_empty() => Ok(Vec::new()),
+                    "table" => Ok(vec![div().class("scroll-container").child(element).into()]),
+                    "span" => Ok(element.children),
+                    _ => Ok(vec![element.into()]),
+                }
+            }
+
+            fn fold_attr(
+                &mut self,
+                name: String,
+                value: String,
+            ) -> Result<Option<(String, String)>, Self::Error> {
+                Ok((name != "style").then_some((name, value)))
+            }
+        }
+
+        let element = body()
+            .child(p())
+            .child(
+                p().attr("style", "color: red")
+                    .child(span().child("Hello, "))
+                    .child("world"),
+            )
+            .child(table().child(tr().child(td().child("Cell"))))
+            .child(p());
+
+        let fragment = Tidy
+            .fold_fragment(Fragment::from(vec![element.into()]))
+            .unwrap();
+
+        assert_yaml_snapshot!(HtmlElementRenderer::new()
+            .render_fragment_to_string(&fragment)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_visit_context() {
+        #[derive(Default)]
+        struct Outline {
+            context: VisitContext,
+            events: Vec<String>,
+        }
+
+        impl Visitor for Outline {
+            type Error = ();
+
+            fn context(&mut self) -> Option<&mut VisitContext> {
+                Some(&mut self.context)
+            }
+
+            fn enter(&mut self, element: &HtmlElement) -> Result<(), Self::Error> {
+                self.events.push(format!(
+                    "{}enter {} (parent: {:?})",
+                    "  ".repeat(self.context.depth()),
+                    element.tag_name,
+                    self.context.parent()
+                ));
+                Ok(())
+            }
+
+            fn leave(&mut self, element: &HtmlElement) -> Result<(), Self::Error> {
+                self.events.push(format!(
+                    "{}leave {}",
+                    "  ".repeat(self.context.depth()),
+                    element.tag_name
+                ));
+                Ok(())
+            }
+
+            fn visit_text(&mut self, text: &str, _safe: bool) -> Result<(), Self::Error> {
+                self.events.push(format!(
+                    "{}text {text:?} (path: {})",
+                    "  ".repeat(self.context.depth()),
+                    self.context.path().join(" > ")
+                ));
+                Ok(())
+            }
+        }
+
+        let element = article()
+            .child(h1().child("Title"))
+            .child(section().child(p().child("Hello, ").child(em().child("world"))));
+
+        let mut visitor = Outline::default();
+        visitor.visit(&element).unwrap();
+
+        assert_yaml_snapshot!(visitor.events);
+    }
 }
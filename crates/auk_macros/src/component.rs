@@ -0,0 +1,426 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Expr, Field, Fields, GenericParam, Generics, Ident, Type};
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "`Component` can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "`Component` can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut children = None;
+    let mut props = Vec::new();
+    for field in &fields.named {
+        let field = ComponentField::parse(field)?;
+        if field.is_children {
+            if children.is_some() {
+                return Err(syn::Error::new(
+                    field.ident.span(),
+                    "only one field can hold the children of a component",
+                ));
+            }
+
+            children = Some(field.ident);
+        } else {
+            props.push(field);
+        }
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let required = props
+        .iter()
+        .filter(|prop| prop.default.is_none() && !prop.skip)
+        .collect::<Vec<_>>();
+
+    let new_params = required.iter().map(|prop| {
+        let ident = &prop.ident;
+        let ty = &prop.ty;
+        quote_spanned!(ident.span()=> #ident: impl ::core::convert::Into<#ty>)
+    });
+
+    let field_initializers = props
+        .iter()
+        .map(|prop| {
+            let ident = &prop.ident;
+            match &prop.default {
+                None if prop.skip => {
+                    quote_spanned!(ident.span()=> #ident: ::core::default::Default::default())
+                }
+                Some(PropDefault::Trait) => {
+                    quote_spanned!(ident.span()=> #ident: ::core::default::Default::default())
+                }
+                Some(PropDefault::Expr(expr)) => quote_spanned!(expr.span()=> #ident: #expr),
+                None => quote_spanned!(ident.span()=> #ident: ::core::convert::Into::into(#ident)),
+            }
+        })
+        .chain(
+            children
+                .iter()
+                .map(|children| quote!(#children: ::std::vec::Vec::new())),
+        );
+
+    let new_doc = if required.is_empty() {
+        format!("Returns a new [`{name}`].")
+    } else {
+        let required_names = required
+            .iter()
+            .map(|prop| format!("`{}`", prop.ident))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("Returns a new [`{name}`] with the given required props ({required_names}).")
+    };
+
+    let setters = props.iter().filter(|prop| !prop.skip).map(|prop| {
+        let ident = &prop.ident;
+        let ty = &prop.ty;
+        let doc = format!(
+            "Sets the `{}` prop.",
+            ident.to_string().trim_start_matches("r#")
+        );
+
+        quote_spanned! {ident.span()=>
+            #[doc = #doc]
+            #vis fn #ident(mut self, #ident: impl ::core::convert::Into<#ty>) -> Self {
+                self.#ident = ::core::convert::Into::into(#ident);
+                self
+            }
+        }
+    });
+
+    let default_impl = required.is_empty().then(|| {
+        quote! {
+            impl #impl_generics ::core::default::Default for #name #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+        }
+    });
+
+    let with_children_impl = children.as_ref().map(|children| {
+        quote! {
+            impl #impl_generics ::auk::WithChildren for #name #ty_generics #where_clause {
+                fn extend(
+                    &mut self,
+                    children: impl ::core::iter::IntoIterator<Item = ::auk::Element>,
+                ) {
+                    ::core::iter::Extend::extend(&mut self.#children, children)
+                }
+            }
+        }
+    });
+
+    let builder = props_builder(&input, &props, &required);
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #new_doc]
+            #vis fn new(#(#new_params),*) -> Self {
+                Self {
+                    #(#field_initializers),*
+                }
+            }
+
+            #(#setters)*
+        }
+
+        #builder
+
+        #default_impl
+
+        impl #impl_generics ::auk::With for #name #ty_generics #where_clause {}
+
+        #with_children_impl
+    })
+}
+
+/// Returns the props builder that `html!` uses to construct the component.
+///
+/// The builder tracks which of the required props have been set in its type
+/// parameters (`()` while unset, and `(T,)` once set), so that it can only be
+/// built once all of them have been set. The optional props are held as
+/// `Option`s and applied on top of the defaults from `new`.
+fn props_builder(
+    input: &DeriveInput,
+    props: &[ComponentField],
+    required: &[&ComponentField],
+) -> TokenStream {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let builder = format_ident!("__{}Props", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let params = generic_params(&input.generics);
+    let args = generic_args(&input.generics);
+
+    let states = (0..required.len())
+        .map(|index| format_ident!("__Required{}", index))
+        .collect::<Vec<_>>();
+    let unset = required.iter().map(|_| quote!(()));
+    let set = required
+        .iter()
+        .map(|prop| {
+            let ty = &prop.ty;
+            quote!((#ty,))
+        })
+        .collect::<Vec<_>>();
+
+    let optional = props
+        .iter()
+        .filter(|prop| prop.default.is_some() && !prop.skip)
+        .collect::<Vec<_>>();
+
+    let required_fields = required.iter().zip(&states).map(|(prop, state)| {
+        let ident = &prop.ident;
+        quote!(#ident: #state)
+    });
+    let optional_fields = optional.iter().map(|prop| {
+        let ident = &prop.ident;
+        let ty = &prop.ty;
+        quote!(#ident: ::core::option::Option<#ty>)
+    });
+
+    let required_idents = required.iter().map(|prop| &prop.ident).collect::<Vec<_>>();
+    let optional_idents = optional.iter().map(|prop| &prop.ident).collect::<Vec<_>>();
+
+    let required_setters = required.iter().enumerate().map(|(index, prop)| {
+        let ident = &prop.ident;
+        let ty = &prop.ty;
+        let doc = format!(
+            "Sets the required `{}` prop.",
+            ident.to_string().trim_start_matches("r#")
+        );
+
+        let other_states = states
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != index)
+            .map(|(_, state)| state);
+        let before = states.iter().enumerate().map(|(other, state)| {
+            if other == index {
+                quote!(())
+            } else {
+                quote!(#state)
+            }
+        });
+        let after = states.iter().enumerate().map(|(other, state)| {
+            if other == index {
+                set[index].clone()
+            } else {
+                quote!(#state)
+            }
+        });
+        let other_fields = required
+            .iter()
+            .map(|prop| &prop.ident)
+            .chain(optional_idents.iter().copied())
+            .filter(|other| *other != ident);
+
+        quote_spanned! {ident.span()=>
+            #[allow(dead_code)]
+            impl<#(#params,)* #(#other_states),*> #builder<#(#args,)* #(#before),*> #where_clause {
+                #[doc = #doc]
+                #vis fn #ident(
+                    self,
+                    #ident: impl ::core::convert::Into<#ty>,
+                ) -> #builder<#(#args,)* #(#after),*> {
+                    #builder {
+                        #ident: (::core::convert::Into::into(#ident),),
+                        #(#other_fields: self.#other_fields,)*
+                        __component: ::core::marker::PhantomData,
+                    }
+                }
+            }
+        }
+    });
+
+    let optional_setters = optional.iter().map(|prop| {
+        let ident = &prop.ident;
+        let ty = &prop.ty;
+        let doc = format!(
+            "Sets the `{}` prop.",
+            ident.to_string().trim_start_matches("r#")
+        );
+
+        quote_spanned! {ident.span()=>
+            #[doc = #doc]
+            #vis fn #ident(mut self, #ident: impl ::core::convert::Into<#ty>) -> Self {
+                self.#ident = ::core::option::Option::Some(::core::convert::Into::into(#ident));
+                self
+            }
+        }
+    });
+
+    let doc = format!("The props of a [`{name}`], as set by the `html!` macro.");
+
+    quote! {
+        #[doc = #doc]
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        #vis struct #builder<#(#params,)* #(#states),*> #where_clause {
+            #(#required_fields,)*
+            #(#optional_fields,)*
+            __component: ::core::marker::PhantomData<fn() -> #name #ty_generics>,
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = "Returns the props builder used by the `html!` macro."]
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            #vis fn __auk_props() -> #builder<#(#args,)* #(#unset),*> {
+                #builder {
+                    #(#required_idents: (),)*
+                    #(#optional_idents: ::core::option::Option::None,)*
+                    __component: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        #(#required_setters)*
+
+        #[allow(dead_code)]
+        impl<#(#params,)* #(#states),*> #builder<#(#args,)* #(#states),*> #where_clause {
+            #(#optional_setters)*
+        }
+
+        impl #impl_generics ::auk::__private::BuildComponent for #builder<#(#args,)* #(#set),*> #where_clause {
+            type Component = #name #ty_generics;
+
+            fn build(self) -> Self::Component {
+                #[allow(unused_mut)]
+                let mut component = <#name #ty_generics>::new(#(self.#required_idents.0),*);
+                #(
+                    if let ::core::option::Option::Some(#optional_idents) = self.#optional_idents {
+                        component.#optional_idents = #optional_idents;
+                    }
+                )*
+                component
+            }
+        }
+    }
+}
+
+/// Returns the generic parameters, without their defaults.
+///
+/// Defaults have to be removed, as more parameters are added after them.
+fn generic_params(generics: &Generics) -> Vec<GenericParam> {
+    generics
+        .params
+        .iter()
+        .cloned()
+        .map(|mut param| {
+            match &mut param {
+                GenericParam::Type(param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                }
+                GenericParam::Const(param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                }
+                GenericParam::Lifetime(_) => {}
+            }
+
+            param
+        })
+        .collect()
+}
+
+/// Returns the generic parameters as arguments (e.g., `'a, T, N`).
+fn generic_args(generics: &Generics) -> Vec<TokenStream> {
+    generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Type(param) => param.ident.to_token_stream(),
+            GenericParam::Const(param) => param.ident.to_token_stream(),
+            GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+        })
+        .collect()
+}
+
+enum PropDefault {
+    /// `#[prop(default)]`
+    Trait,
+    /// `#[prop(default = expr)]`
+    Expr(Expr),
+}
+
+struct ComponentField {
+    ident: Ident,
+    ty: Type,
+    default: Option<PropDefault>,
+    /// Whether the field is internal state rather than a prop (`#[prop(skip)]`).
+    skip: bool,
+    is_children: bool,
+}
+
+impl ComponentField {
+    fn parse(field: &Field) -> syn::Result<Self> {
+        let ident = field.ident.clone().expect("field is named");
+
+        let mut default = None;
+        let mut skip = false;
+        let mut is_children = ident == "children";
+
+        for attr in &field.attrs {
+            if attr.path().is_ident("children") {
+                attr.meta.require_path_only()?;
+                is_children = true;
+            } else if attr.path().is_ident("prop") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("default") {
+                        default = Some(if meta.input.peek(syn::Token![=]) {
+                            PropDefault::Expr(meta.value()?.parse()?)
+                        } else {
+                            PropDefault::Trait
+                        });
+
+                        Ok(())
+                    } else if meta.path.is_ident("skip") {
+                        skip = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported prop attribute, expected `default` or `skip`"))
+                    }
+                })?;
+            }
+        }
+
+        if is_children && default.is_some() {
+            return Err(syn::Error::new(
+                ident.span(),
+                "the children of a component cannot have a default",
+            ));
+        }
+
+        if is_children && skip {
+            return Err(syn::Error::new(
+                ident.span(),
+                "the children of a component cannot be skipped",
+            ));
+        }
+
+        Ok(Self {
+            ident,
+            ty: field.ty.clone(),
+            default,
+            skip,
+            is_children,
+        })
+    }
+}
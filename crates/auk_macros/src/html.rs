@@ -0,0 +1,599 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::spanned::Spanned;
+use syn::{braced, token, Expr, Ident, Lit, LitStr, Pat, Token};
+
+/// The tag names of the [void elements](https://developer.mozilla.org/en-US/docs/Glossary/Void_element),
+/// which may be written without being closed.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// The tag names that `auk` has an element constructor for.
+///
+/// This must be kept in sync with the `html_elements!` invocation in `auk`.
+/// Any other tag name is constructed using `HtmlElement::new`.
+const AUK_ELEMENTS: &[&str] = &[
+    "a",
+    "abbr",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "base",
+    "bdi",
+    "bdo",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "datalist",
+    "dd",
+    "del",
+    "dfn",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "map",
+    "mark",
+    "math",
+    "menu",
+    "meta",
+    "meter",
+    "nav",
+    "noscript",
+    "object",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "picture",
+    "portal",
+    "pre",
+    "progress",
+    "q",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "script",
+    "search",
+    "section",
+    "select",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "style",
+    "sub",
+    "sup",
+    "svg",
+    "table",
+    "tbody",
+    "td",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+    "details",
+    "dialog",
+    "summary",
+    "slot",
+    "template",
+];
+
+pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let nodes = Parser::parse2(parse_nodes, input)?;
+
+    Ok(match nodes.as_slice() {
+        [Node::Element(element)] => element.to_token_stream(),
+        _ => fragment(&nodes),
+    })
+}
+
+fn parse_nodes(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    while !input.is_empty() {
+        nodes.push(input.parse()?);
+    }
+
+    Ok(nodes)
+}
+
+/// Returns an expression that builds a `Fragment` from the given nodes.
+fn fragment(nodes: &[Node]) -> TokenStream {
+    let fragment = Ident::new("__auk_fragment", Span::mixed_site());
+    let children = nodes.iter().map(|node| node.append_to(&fragment));
+
+    quote! {
+        {
+            let #fragment = ::auk::Fragment::new();
+            #(#children)*
+            #fragment
+        }
+    }
+}
+
+enum Node {
+    Element(ElementNode),
+    Text(LitStr),
+    Expr(Expr),
+    If(IfNode),
+    For(ForNode),
+}
+
+impl Node {
+    /// Returns a statement that appends this node to the children of `parent`.
+    fn append_to(&self, parent: &Ident) -> TokenStream {
+        let child = match self {
+            Self::Element(element) => element.to_token_stream(),
+            Self::Text(text) => text.to_token_stream(),
+            Self::Expr(expr) => expr.to_token_stream(),
+            Self::If(if_node) => if_node.to_token_stream(),
+            Self::For(for_node) => for_node.to_token_stream(),
+        };
+
+        quote_spanned! {self.span()=>
+            let #parent = ::auk::WithChildren::child(#parent, #child);
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            Self::Element(element) => element.name.span,
+            Self::Text(text) => text.span(),
+            Self::Expr(expr) => expr.span(),
+            Self::If(if_node) => if_node.if_token.span,
+            Self::For(for_node) => for_node.for_token.span,
+        }
+    }
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(Token![<]) {
+            if input.peek2(Token![/]) {
+                let _: Token![<] = input.parse()?;
+                let _: Token![/] = input.parse()?;
+                let name: Name = input.parse()?;
+                return Err(syn::Error::new(
+                    name.span,
+                    format!("unexpected closing tag `</{}>`", name.value),
+                ));
+            }
+
+            input.parse().map(Self::Element)
+        } else if lookahead.peek(LitStr) {
+            input.parse().map(Self::Text)
+        } else if lookahead.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            content.parse().map(Self::Expr)
+        } else if lookahead.peek(Token![if]) {
+            input.parse().map(Self::If)
+        } else if lookahead.peek(Token![for]) {
+            input.parse().map(Self::For)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// A tag or attribute name, which may contain `-` and `:` (e.g., `aria-label`).
+struct Name {
+    value: String,
+    span: Span,
+    /// The name as an identifier, if it is a single identifier.
+    ident: Option<Ident>,
+}
+
+impl Parse for Name {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let first = Ident::parse_any(input)?;
+
+        let mut value = first.to_string();
+        let mut span = first.span();
+        let mut ident = Some(first);
+
+        while input.peek(Token![-]) || input.peek(Token![:]) {
+            if input.peek(Token![-]) {
+                let _: Token![-] = input.parse()?;
+                value.push('-');
+            } else {
+                let _: Token![:] = input.parse()?;
+                value.push(':');
+            }
+
+            let segment = Ident::parse_any(input)?;
+            value.push_str(&segment.to_string());
+            span = span.join(segment.span()).unwrap_or(span);
+            ident = None;
+        }
+
+        Ok(Self { value, span, ident })
+    }
+}
+
+struct ElementNode {
+    name: Name,
+    attrs: Vec<AttributeNode>,
+    children: Vec<Node>,
+}
+
+impl ElementNode {
+    fn is_component(&self) -> bool {
+        self.name.ident.is_some() && self.name.value.starts_with(char::is_uppercase)
+    }
+}
+
+impl Parse for ElementNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let _: Token![<] = input.parse()?;
+        let name: Name = input.parse()?;
+
+        let mut attrs = Vec::new();
+        while !(input.peek(Token![>]) || input.peek(Token![/])) {
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    name.span,
+                    format!("unclosed tag `<{}>`", name.value),
+                ));
+            }
+
+            attrs.push(input.parse()?);
+        }
+
+        if input.peek(Token![/]) {
+            let _: Token![/] = input.parse()?;
+            let _: Token![>] = input.parse()?;
+
+            return Ok(Self {
+                name,
+                attrs,
+                children: Vec::new(),
+            });
+        }
+
+        let _: Token![>] = input.parse()?;
+
+        if VOID_ELEMENTS.contains(&name.value.as_str()) {
+            return Ok(Self {
+                name,
+                attrs,
+                children: Vec::new(),
+            });
+        }
+
+        let mut children = Vec::new();
+        while !(input.peek(Token![<]) && input.peek2(Token![/])) {
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    name.span,
+                    format!("missing closing tag `</{}>`", name.value),
+                ));
+            }
+
+            children.push(input.parse()?);
+        }
+
+        let _: Token![<] = input.parse()?;
+        let _: Token![/] = input.parse()?;
+        let closing_name: Name = input.parse()?;
+        if closing_name.value != name.value {
+            return Err(syn::Error::new(
+                closing_name.span,
+                format!("expected closing tag `</{}>`", name.value),
+            ));
+        }
+        let _: Token![>] = input.parse()?;
+
+        Ok(Self {
+            name,
+            attrs,
+            children,
+        })
+    }
+}
+
+impl ToTokens for ElementNode {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let span = self.name.span;
+        let element = Ident::new("__auk_element", Span::mixed_site());
+
+        let constructor = match &self.name.ident {
+            Some(ident) if self.is_component() => quote_spanned! {span=>
+                {
+                    #[allow(unused_imports)]
+                    use ::auk::__private::DefaultComponent as _;
+                    #ident::__auk_props()
+                }
+            },
+            Some(ident) if AUK_ELEMENTS.contains(&self.name.value.as_str()) => {
+                quote_spanned!(span=> ::auk::#ident())
+            }
+            _ => {
+                let name = &self.name.value;
+                quote_spanned!(span=> ::auk::HtmlElement::new(#name))
+            }
+        };
+
+        let attrs = self.attrs.iter().map(|attr| {
+            let value = match &attr.value {
+                Some(AttributeValue::Lit(lit)) => lit.to_token_stream(),
+                Some(AttributeValue::Expr(expr)) => expr.to_token_stream(),
+                None if self.is_component() => quote_spanned!(attr.name.span=> true),
+                None => quote_spanned!(attr.name.span=> ""),
+            };
+
+            match &attr.name.ident {
+                Some(ident) if self.is_component() => quote_spanned! {attr.name.span=>
+                    let #element = #element.#ident(#value);
+                },
+                _ => {
+                    let name = &attr.name.value;
+                    quote_spanned! {attr.name.span=>
+                        let #element = #element.attr(#name, #value);
+                    }
+                }
+            }
+        });
+
+        let build = self.is_component().then(|| {
+            quote_spanned! {span=>
+                let #element = ::auk::__private::BuildComponent::build(#element);
+            }
+        });
+
+        let children = self.children.iter().map(|child| child.append_to(&element));
+
+        tokens.extend(quote! {
+            {
+                let #element = #constructor;
+                #(#attrs)*
+                #build
+                #(#children)*
+                #element
+            }
+        });
+    }
+}
+
+struct AttributeNode {
+    name: Name,
+    value: Option<AttributeValue>,
+}
+
+enum AttributeValue {
+    Lit(Lit),
+    Expr(Expr),
+}
+
+impl Parse for AttributeNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Name = input.parse()?;
+
+        if !input.peek(Token![=]) {
+            return Ok(Self { name, value: None });
+        }
+
+        let _: Token![=] = input.parse()?;
+
+        let value = if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            AttributeValue::Expr(content.parse()?)
+        } else {
+            AttributeValue::Lit(input.parse()?)
+        };
+
+        Ok(Self {
+            name,
+            value: Some(value),
+        })
+    }
+}
+
+struct IfNode {
+    if_token: Token![if],
+    condition: Expr,
+    then_branch: Vec<Node>,
+    else_branch: Option<ElseBranch>,
+}
+
+enum ElseBranch {
+    If(Box<IfNode>),
+    Else(Vec<Node>),
+}
+
+impl Parse for IfNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let if_token = input.parse()?;
+        let condition = Expr::parse_without_eager_brace(input)?;
+
+        let content;
+        braced!(content in input);
+        let then_branch = parse_nodes(&content)?;
+
+        let else_branch = if input.peek(Token![else]) {
+            let _: Token![else] = input.parse()?;
+
+            if input.peek(Token![if]) {
+                Some(ElseBranch::If(Box::new(input.parse()?)))
+            } else {
+                let content;
+                braced!(content in input);
+                Some(ElseBranch::Else(parse_nodes(&content)?))
+            }
+        } else {
+            None
+        };
+
+        Ok(Self {
+            if_token,
+            condition,
+            then_branch,
+            else_branch,
+        })
+    }
+}
+
+impl ToTokens for IfNode {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let if_token = self.if_token;
+        let condition = &self.condition;
+        let then_branch = fragment(&self.then_branch);
+        let else_branch = match &self.else_branch {
+            Some(ElseBranch::If(if_node)) => if_node.to_token_stream(),
+            Some(ElseBranch::Else(nodes)) => fragment(nodes),
+            None => quote!(::auk::Fragment::new()),
+        };
+
+        tokens.extend(quote! {
+            #if_token #condition #then_branch else { #else_branch }
+        });
+    }
+}
+
+struct ForNode {
+    for_token: Token![for],
+    pat: Pat,
+    expr: Expr,
+    body: Vec<Node>,
+}
+
+impl Parse for ForNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let for_token = input.parse()?;
+        let pat = Pat::parse_multi_with_leading_vert(input)?;
+        let _: Token![in] = input.parse()?;
+        let expr = Expr::parse_without_eager_brace(input)?;
+
+        let content;
+        braced!(content in input);
+        let body = parse_nodes(&content)?;
+
+        Ok(Self {
+            for_token,
+            pat,
+            expr,
+            body,
+        })
+    }
+}
+
+impl ToTokens for ForNode {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let for_token = self.for_token;
+        let pat = &self.pat;
+        let expr = &self.expr;
+        let fragment_ident = Ident::new("__auk_fragment", Span::mixed_site());
+        let body = fragment(&self.body);
+
+        tokens.extend(quote! {
+            {
+                let mut #fragment_ident = ::auk::Fragment::new();
+                #for_token #pat in #expr {
+                    #fragment_ident = ::auk::WithChildren::child(#fragment_ident, #body);
+                }
+                #fragment_ident
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+
+    fn expand_error(input: TokenStream) -> String {
+        expand(input).err().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_mismatched_closing_tag() {
+        assert_eq!(
+            expand_error(quote!(<div><p>"Hello"</div></p>)),
+            "expected closing tag `</p>`"
+        );
+    }
+
+    #[test]
+    fn test_missing_closing_tag() {
+        assert_eq!(
+            expand_error(quote!(<div><p>"Hello"</p>)),
+            "missing closing tag `</div>`"
+        );
+    }
+
+    #[test]
+    fn test_unexpected_closing_tag() {
+        assert_eq!(
+            expand_error(quote!(<br></br>)),
+            "unexpected closing tag `</br>`"
+        );
+    }
+
+    #[test]
+    fn test_unexpected_token() {
+        assert_eq!(
+            expand_error(quote!(<p>Hello</p>)),
+            "expected one of: `<`, string literal, curly braces, `if`, `for`"
+        );
+    }
+}
@@ -0,0 +1,175 @@
+#![doc = include_str!("../README.md")]
+#![deny(missing_docs)]
+
+mod component;
+mod html;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Writes an element using HTML-like syntax.
+///
+/// The macro expands to the equivalent `auk` builder calls, so the result is a
+/// regular [`HtmlElement`](https://docs.rs/auk/latest/auk/struct.HtmlElement.html)
+/// when given a single root element, or a
+/// [`Fragment`](https://docs.rs/auk/latest/auk/struct.Fragment.html) otherwise.
+///
+/// # Syntax
+///
+/// - Elements are written as `<div>...</div>`, or as `<br />` when they have
+///   no children. Void elements (e.g., `<br>`) do not need to be closed.
+/// - Attributes are written as `name="value"`, `name={expr}`, or just `name`
+///   for boolean attributes. Names may contain `-` and `:` (e.g.,
+///   `aria-label` or `xlink:href`).
+/// - Text is written as a string literal: `"Hello, world!"`.
+/// - Any expression that can be a child (text, elements, fragments, and
+///   components) can be interpolated using braces: `{name}`.
+/// - `if`/`else if`/`else` and `for` can be used to add children
+///   conditionally or repeatedly.
+/// - Tags that `auk` does not have a constructor for (e.g., `<path>` or
+///   custom elements) are constructed using `HtmlElement::new`.
+/// - Tags starting with an uppercase letter are components, with attributes
+///   setting the props of the same name. Components deriving
+///   [`Component`](derive@Component) are built from their props, so leaving
+///   out a required prop is a compile error. Other components are constructed
+///   using `Default`, with attributes becoming calls to setter methods of the
+///   same name.
+///
+/// # Example
+///
+/// ```
+/// use auk::renderer::HtmlElementRenderer;
+/// use auk_macros::html;
+///
+/// let items = ["One", "Two"];
+/// let show_footer = true;
+///
+/// let element = html! {
+///     <div class="card">
+///         <h1>"Items"</h1>
+///         <ul>
+///             for item in items {
+///                 <li>{item}</li>
+///             }
+///         </ul>
+///         if show_footer {
+///             <footer>"That's all!"</footer>
+///         }
+///     </div>
+/// };
+///
+/// assert_eq!(
+///     HtmlElementRenderer::new().render_to_string(&element).unwrap(),
+///     r#"<div class="card"><h1>Items</h1><ul><li>One</li><li>Two</li></ul><footer>That's all!</footer></div>"#
+/// );
+/// ```
+#[proc_macro]
+pub fn html(input: TokenStream) -> TokenStream {
+    html::expand(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives the boilerplate for a component.
+///
+/// This generates:
+///
+/// - A `new` constructor, which takes the required props as arguments.
+/// - A builder-style setter for each prop.
+/// - An implementation of `Default`, if there are no required props.
+/// - An implementation of `With`.
+/// - An implementation of `WithChildren`, if the component has a field for
+///   its children.
+/// - A hidden props builder, which [`html!`] uses to build the component
+///   from its attributes.
+///
+/// The component still needs to implement `Render` itself.
+///
+/// # Props
+///
+/// Every field is a prop, except for the field holding the children of the
+/// component. This is the field named `children`, or the field marked with
+/// `#[children]`, and it must be a `Vec<Element>`.
+///
+/// Props are required unless they have a default:
+///
+/// - `#[prop(default)]` defaults the prop using [`Default`].
+/// - `#[prop(default = expr)]` defaults the prop to the given expression.
+///
+/// Fields marked with `#[prop(skip)]` are internal state rather than props.
+/// They have no setter and are initialized using [`Default`], or using the
+/// given expression when combined with `default = expr`.
+///
+/// Since required props are arguments to `new`, leaving one out is a compile
+/// error. The same goes for the attributes of a component in [`html!`]:
+///
+/// ```
+/// # use auk::*;
+/// # use auk_macros::{html, Component};
+/// #[derive(Component)]
+/// struct Greeting {
+///     name: String,
+///     #[prop(default)]
+///     excited: bool,
+/// }
+/// # impl Render for Greeting {
+/// #     fn render(self) -> impl Into<HtmlElement> {
+/// #         p().child(self.name)
+/// #     }
+/// # }
+///
+/// let element = html! {
+///     <div>
+///         <Greeting name="Auk" excited />
+///     </div>
+/// };
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use auk::*;
+/// use auk::renderer::HtmlElementRenderer;
+/// use auk_macros::Component;
+///
+/// #[derive(Component)]
+/// pub struct Alert {
+///     title: String,
+///     #[prop(default = "info".to_string())]
+///     level: String,
+///     #[prop(default)]
+///     dismissible: bool,
+///     children: Vec<Element>,
+/// }
+///
+/// impl Render for Alert {
+///     fn render(self) -> impl Into<HtmlElement> {
+///         div()
+///             .class(format!("alert alert-{}", self.level))
+///             .child(strong().child(self.title))
+///             .children(self.children)
+///             .with(|alert| {
+///                 if self.dismissible {
+///                     alert.child(button().child("Dismiss"))
+///                 } else {
+///                     alert
+///                 }
+///             })
+///     }
+/// }
+///
+/// let alert = Alert::new("Heads up!").level("warning").child(p().child("Something happened."));
+///
+/// assert_eq!(
+///     HtmlElementRenderer::new().render_to_string(&alert.into()).unwrap(),
+///     r#"<div class="alert alert-warning"><strong>Heads up!</strong><p>Something happened.</p></div>"#
+/// );
+/// ```
+#[proc_macro_derive(Component, attributes(prop, children))]
+pub fn derive_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    component::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
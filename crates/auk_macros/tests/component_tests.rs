@@ -0,0 +1,137 @@
+use auk::renderer::HtmlElementRenderer;
+use auk::*;
+use auk_macros::{html, Component};
+use insta::assert_yaml_snapshot;
+
+#[derive(Component)]
+struct Card {
+    title: String,
+    #[prop(default = "default".to_string())]
+    variant: String,
+    #[prop(default)]
+    subtitle: Option<String>,
+    #[children]
+    body: Vec<Element>,
+}
+
+impl Render for Card {
+    fn render(self) -> impl Into<HtmlElement> {
+        article()
+            .class(format!("card card-{}", self.variant))
+            .child(h2().child(self.title))
+            .with(|card| match self.subtitle {
+                Some(subtitle) => card.child(p().class("subtitle").child(subtitle)),
+                None => card,
+            })
+            .children(self.body)
+    }
+}
+
+#[derive(Component)]
+struct Badge {
+    #[prop(default)]
+    count: u32,
+}
+
+impl Render for Badge {
+    fn render(self) -> impl Into<HtmlElement> {
+        span().class("badge").child(self.count.to_string())
+    }
+}
+
+#[derive(Component)]
+struct Tag {
+    label: String,
+    #[prop(skip, default = vec!["tag".to_string()])]
+    classes: Vec<String>,
+    #[prop(skip)]
+    title: Option<String>,
+}
+
+impl Tag {
+    fn class(mut self, class: impl Into<String>) -> Self {
+        self.classes.push(class.into());
+        self
+    }
+}
+
+impl Render for Tag {
+    fn render(self) -> impl Into<HtmlElement> {
+        span()
+            .class(self.classes.join(" "))
+            .title(self.title)
+            .child(self.label)
+    }
+}
+
+fn render(element: impl Into<HtmlElement>) -> String {
+    HtmlElementRenderer::new()
+        .render_to_string(&element.into())
+        .unwrap()
+}
+
+#[test]
+fn test_component_required_and_default_props() {
+    let card = Card::new("Title").child(p().child("Body"));
+
+    assert_yaml_snapshot!(render(card));
+}
+
+#[test]
+fn test_component_setters() {
+    let card = Card::new("Title")
+        .variant("primary")
+        .subtitle("Subtitle".to_string())
+        .title("New title")
+        .children([p().child("One"), p().child("Two")]);
+
+    assert_yaml_snapshot!(render(card));
+}
+
+#[test]
+fn test_component_without_children() {
+    assert_yaml_snapshot!(render(Badge::default().count(3u32)));
+}
+
+#[test]
+fn test_component_skipped_fields() {
+    let tag = Tag::new("New").class("tag-new").label("Newest");
+
+    assert_eq!(render(tag), r#"<span class="tag tag-new">Newest</span>"#);
+}
+
+#[test]
+fn test_component_in_html_macro() {
+    let element = html! {
+        <section>
+            <Badge count={7u32} />
+            <Badge />
+        </section>
+    };
+
+    assert_yaml_snapshot!(render(element));
+}
+
+#[test]
+fn test_component_with_required_props_in_html_macro() {
+    let title = "Title";
+
+    let element = html! {
+        <section>
+            <Card title={title} subtitle={Some("Subtitle".to_string())}>
+                <p>"Body"</p>
+            </Card>
+            <Card variant="primary" title="Other" />
+        </section>
+    };
+
+    let expected = section()
+        .child(
+            Card::new(title)
+                .subtitle(Some("Subtitle".to_string()))
+                .child(p().child("Body")),
+        )
+        .child(Card::new("Other").variant("primary"));
+
+    assert_eq!(render(element), render(expected));
+}
@@ -0,0 +1,157 @@
+use auk::renderer::HtmlElementRenderer;
+use auk::*;
+use auk_macros::html;
+use insta::assert_yaml_snapshot;
+
+fn render(element: &HtmlElement) -> String {
+    HtmlElementRenderer::new()
+        .render_to_string(element)
+        .unwrap()
+}
+
+fn render_fragment(fragment: &Fragment) -> String {
+    HtmlElementRenderer::new()
+        .render_fragment_to_string(fragment)
+        .unwrap()
+}
+
+struct Greeting {
+    name: String,
+    excited: bool,
+}
+
+impl Default for Greeting {
+    fn default() -> Self {
+        Self {
+            name: "world".to_string(),
+            excited: false,
+        }
+    }
+}
+
+impl Greeting {
+    fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    fn excited(mut self, excited: bool) -> Self {
+        self.excited = excited;
+        self
+    }
+}
+
+impl Render for Greeting {
+    fn render(self) -> impl Into<HtmlElement> {
+        let punctuation = if self.excited { "!" } else { "." };
+
+        p().child(format!("Hello, {}{punctuation}", self.name))
+    }
+}
+
+#[test]
+fn test_html_matches_builder() {
+    let title = "Auk";
+
+    let element = html! {
+        <div class="outer">
+            <h1 id="title">{title}</h1>
+            <p>"Hello, " <em>"world"</em> "!"</p>
+        </div>
+    };
+
+    let expected = div()
+        .class("outer")
+        .child(h1().id("title").child(title))
+        .child(p().child("Hello, ").child(em().child("world")).child("!"));
+
+    assert_eq!(element, expected);
+}
+
+#[test]
+fn test_html_attributes() {
+    let href = "/";
+
+    let element = html! {
+        <form>
+            <label for="name" data-tooltip="Your name">"Name"</label>
+            <input type="text" id="name" required>
+            <input type="checkbox" checked />
+            <a href={href}>"Home"</a>
+            <svg xmlns:xlink="http://www.w3.org/1999/xlink"></svg>
+            <my-element count={3.to_string()}></my-element>
+        </form>
+    };
+
+    assert_yaml_snapshot!(render(&element));
+}
+
+#[test]
+fn test_html_elements_without_constructors() {
+    let element = html! {
+        <div>
+            <svg viewBox="0 0 10 10">
+                <circle cx="5" cy="5" r="4" />
+                <path d="M 0 0 L 10 10" />
+            </svg>
+            <foo></foo>
+        </div>
+    };
+
+    assert_eq!(
+        render(&element),
+        r#"<div><svg viewBox="0 0 10 10"><circle cx="5" cy="5" r="4"/><path d="M 0 0 L 10 10"/></svg><foo></foo></div>"#
+    );
+}
+
+#[test]
+fn test_html_control_flow() {
+    let items = ["One", "Two", "Three"];
+    let selected = 1;
+    let logged_in = false;
+
+    let element = html! {
+        <nav>
+            <ul>
+                for (index, item) in items.iter().enumerate() {
+                    if index == selected {
+                        <li class="selected">{*item}</li>
+                    } else {
+                        <li>{*item}</li>
+                    }
+                }
+            </ul>
+            if logged_in {
+                <a href="/logout">"Log out"</a>
+            } else if items.len() > 10 {
+                "Too many items"
+            } else {
+                <a href="/login">"Log in"</a>
+            }
+        </nav>
+    };
+
+    assert_yaml_snapshot!(render(&element));
+}
+
+#[test]
+fn test_html_fragment() {
+    let fragment = html! {
+        <h1>"Title"</h1>
+        <p>"Body"</p>
+    };
+
+    assert_yaml_snapshot!(render_fragment(&fragment));
+}
+
+#[test]
+fn test_html_components() {
+    let element = html! {
+        <div>
+            <Greeting />
+            <Greeting name="Auk" excited />
+        </div>
+    };
+
+    assert_yaml_snapshot!(render(&element));
+}
@@ -0,0 +1,9 @@
+use auk_macros::html;
+
+struct Theme;
+
+fn main() {
+    let _ = html! {
+        <div class={Theme}></div>
+    };
+}
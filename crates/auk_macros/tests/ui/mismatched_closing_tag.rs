@@ -0,0 +1,9 @@
+use auk_macros::html;
+
+fn main() {
+    let _ = html! {
+        <div>
+            <p>"Hello"</span>
+        </div>
+    };
+}
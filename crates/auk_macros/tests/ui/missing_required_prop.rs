@@ -0,0 +1,23 @@
+use auk::*;
+use auk_macros::{html, Component};
+
+#[derive(Component)]
+struct Greeting {
+    name: String,
+    #[prop(default)]
+    excited: bool,
+}
+
+impl Render for Greeting {
+    fn render(self) -> impl Into<HtmlElement> {
+        p().child(format!("Hello, {}{}", self.name, if self.excited { "!" } else { "." }))
+    }
+}
+
+fn main() {
+    let _ = Greeting::new().excited(true);
+
+    let _ = html! {
+        <Greeting excited />
+    };
+}
@@ -0,0 +1,33 @@
+use auk::*;
+use auk_macros::html;
+
+struct Greeting {
+    name: String,
+}
+
+impl Default for Greeting {
+    fn default() -> Self {
+        Self {
+            name: "world".to_string(),
+        }
+    }
+}
+
+impl Greeting {
+    fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+}
+
+impl Render for Greeting {
+    fn render(self) -> impl Into<HtmlElement> {
+        p().child(format!("Hello, {}!", self.name))
+    }
+}
+
+fn main() {
+    let _ = html! {
+        <Greeting nmae="Auk" />
+    };
+}
@@ -17,6 +17,7 @@ pub use crate::table_of_contents::*;
 /// Renders the provided Markdown text into [`Element`]s and its [`TableOfContents`].
 ///
 /// Uses the provided [`MarkdownComponents`] to render specific Markdown elements.
+#[allow(clippy::borrowed_box)]
 pub fn render_markdown(
     text: &str,
     components: &Box<dyn MarkdownComponents>,
@@ -30,7 +31,7 @@ pub fn render_markdown(
 
     let parser = md::Parser::new_ext(text, options);
 
-    let mut elements = HtmlElementWriter::new(parser, components).run();
+    let mut elements = HtmlElementWriter::new(parser, components.as_ref()).run();
     let table_of_contents = TableOfContents::from_markdown(&mut elements);
 
     (elements, table_of_contents)
@@ -46,7 +47,7 @@ where
     I: Iterator<Item = Event<'a>>,
 {
     input: I,
-    components: &'a Box<dyn MarkdownComponents>,
+    components: &'a dyn MarkdownComponents,
     elements: Vec<Element>,
     current_element_stack: VecDeque<HtmlElement>,
     table_state: TableState,
@@ -59,7 +60,7 @@ impl<'a, I> HtmlElementWriter<'a, I>
 where
     I: Iterator<Item = Event<'a>>,
 {
-    pub fn new(input: I, components: &'a Box<dyn MarkdownComponents>) -> Self {
+    pub fn new(input: I, components: &'a dyn MarkdownComponents) -> Self {
         Self {
             input,
             components,
@@ -128,7 +129,7 @@ where
         let mut nest = 0;
         let mut raw_text = String::new();
 
-        while let Some(event) = self.input.next() {
+        for event in self.input.by_ref() {
             match event {
                 Event::Start(_) => {
                     nest += 1;
@@ -200,7 +201,7 @@ where
                 };
 
                 self.push(
-                    heading.id::<String>(id.map(Into::into)).class::<String>(
+                    heading.id(id.map(|id| id.to_string())).class(
                         Some(classes)
                             .filter(|classes| !classes.is_empty())
                             .map(|classes| classes.into_iter().collect::<Vec<_>>().join(" ")),
@@ -243,7 +244,7 @@ where
                 self.push(self.components.code(CodeProps { language }));
             }
             Tag::List(Some(1)) => self.push(self.components.ol()),
-            Tag::List(Some(start)) => self.push(self.components.ol().start(start.to_string())),
+            Tag::List(Some(start)) => self.push(self.components.ol().start(start)),
             Tag::List(None) => self.push(self.components.ul()),
             Tag::Item => self.push(self.components.li()),
             Tag::Emphasis => self.push(self.components.em()),
@@ -347,6 +348,7 @@ where
 #[cfg(test)]
 mod tests {
     use auk::renderer::HtmlElementRenderer;
+    use auk::Fragment;
     use indoc::indoc;
 
     use super::*;
@@ -355,16 +357,9 @@ mod tests {
         let (elements, _table_of_contents) =
             render_markdown(text, &DefaultMarkdownComponents.boxed());
 
-        elements
-            .into_iter()
-            .map(|element| match element {
-                Element::Text(element) => element.text,
-                Element::Html(element) => HtmlElementRenderer::new()
-                    .render_to_string(&element)
-                    .unwrap(),
-            })
-            .collect::<Vec<_>>()
-            .join("")
+        HtmlElementRenderer::new()
+            .render_fragment_to_string(&Fragment::from(elements))
+            .unwrap()
     }
 
     #[test]
@@ -177,15 +177,12 @@ pub trait MarkdownComponents: Send + Sync {
 
     /// Renders an `<a>`.
     fn a(&self, props: AProps) -> HtmlElement {
-        auk::a().href(props.href).title::<String>(props.title)
+        auk::a().href(props.href).title(props.title)
     }
 
     /// Renders an `<img>`.
     fn img(&self, props: ImgProps) -> HtmlElement {
-        auk::img()
-            .src(props.src)
-            .alt::<String>(props.alt)
-            .title::<String>(props.title)
+        auk::img().src(props.src).alt(props.alt).title(props.title)
     }
 
     /// Renders a `<br>`.
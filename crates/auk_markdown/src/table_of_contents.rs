@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use auk::visitor::{noop_visit_element, MutVisitor};
+use auk::visitor::{MutVisitor, VisitContext};
 use auk::{Element, HtmlElement};
 use derive_more::{Deref, DerefMut};
 use slug::slugify;
@@ -73,7 +73,7 @@ pub struct Heading {
 struct HeadingIdentifier {
     headings: Vec<Heading>,
     heading_id_counts: HashMap<String, usize>,
-    inside_header: bool,
+    context: VisitContext,
     title: Option<String>,
 }
 
@@ -82,69 +82,76 @@ impl HeadingIdentifier {
         Self {
             headings: Vec::new(),
             heading_id_counts: HashMap::new(),
-            inside_header: false,
+            context: VisitContext::new(),
             title: None,
         }
     }
+
+    fn heading_level(tag_name: &str) -> Option<u32> {
+        match tag_name {
+            "h2" => Some(2),
+            "h3" => Some(3),
+            "h4" => Some(4),
+            "h5" => Some(5),
+            "h6" => Some(6),
+            _ => None,
+        }
+    }
 }
 
 impl MutVisitor for HeadingIdentifier {
     type Error = ();
 
-    fn visit(&mut self, element: &mut HtmlElement) -> Result<(), Self::Error> {
-        match element.tag_name.as_str() {
-            "h2" | "h3" | "h4" | "h5" | "h6" => {
-                self.inside_header = true;
-
-                noop_visit_element(self, element)?;
-
-                if let Some(title) = self.title.take() {
-                    let mut id = slugify(
-                        title
-                            // HACK: Remove undesired remnants from escaping.
-                            // We should figure out how to avoid escaping in the first place.
-                            .replace("&quot;", ""),
-                    );
-
-                    let id_count = self.heading_id_counts.entry(id.clone()).or_insert(0);
-                    if *id_count > 0 {
-                        id.push_str("-");
-                        id.push_str(&id_count.to_string());
-                    }
-
-                    *id_count += 1;
-
-                    if element.attrs.get("id").is_none() {
-                        element.attrs.insert("id".to_string(), id.clone());
-                    }
-
-                    self.headings.push(Heading {
-                        level: match element.tag_name.as_str() {
-                            "h2" => 2,
-                            "h3" => 3,
-                            "h4" => 4,
-                            "h5" => 5,
-                            "h6" => 6,
-                            _ => unreachable!(),
-                        },
-                        id,
-                        title,
-                        children: Vec::new(),
-                    });
-                }
-
-                self.inside_header = false;
+    fn context(&mut self) -> Option<&mut VisitContext> {
+        Some(&mut self.context)
+    }
+
+    fn leave(&mut self, element: &mut HtmlElement) -> Result<(), Self::Error> {
+        let Some(level) = Self::heading_level(&element.tag_name) else {
+            return Ok(());
+        };
+
+        if let Some(title) = self.title.take() {
+            let mut id = slugify(
+                title
+                    // HACK: Remove undesired remnants from escaping.
+                    // We should figure out how to avoid escaping in the first place.
+                    .replace("&quot;", ""),
+            );
+
+            let id_count = self.heading_id_counts.entry(id.clone()).or_insert(0);
+            if *id_count > 0 {
+                id.push('-');
+                id.push_str(&id_count.to_string());
             }
-            _ => {}
+
+            *id_count += 1;
+
+            if element.attrs.get("id").is_none() {
+                element.attrs.insert("id".to_string(), id.clone());
+            }
+
+            self.headings.push(Heading {
+                level,
+                id,
+                title,
+                children: Vec::new(),
+            });
         }
 
-        noop_visit_element(self, element)
+        Ok(())
     }
 
     fn visit_text(&mut self, text: &mut String, _safe: &mut bool) -> Result<(), Self::Error> {
-        if self.inside_header {
+        let inside_heading = self
+            .context
+            .path()
+            .iter()
+            .any(|tag_name| Self::heading_level(tag_name).is_some());
+
+        if inside_heading {
             let mut title = self.title.take().unwrap_or_default();
-            title.push_str(&text);
+            title.push_str(text);
             self.title = Some(title);
         }
 
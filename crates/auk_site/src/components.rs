@@ -1,25 +1,10 @@
 pub use auk::*;
 
+#[derive(Component)]
 pub struct Skeleton {
     children: Vec<Element>,
 }
 
-impl Skeleton {
-    pub fn new() -> Self {
-        Self {
-            children: Vec::new(),
-        }
-    }
-}
-
-impl With for Skeleton {}
-
-impl WithChildren for Skeleton {
-    fn extend(&mut self, children: impl IntoIterator<Item = Element>) {
-        self.children.extend(children)
-    }
-}
-
 impl Render for Skeleton {
     fn render(self) -> impl Into<HtmlElement> {
         html()
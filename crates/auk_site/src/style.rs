@@ -1,4 +1,4 @@
-use auk::With;
+use auk::{AttrValue, With};
 use auk_plumage::style_methods;
 
 pub fn class() -> StyleBuilder {
@@ -19,6 +19,12 @@ impl From<StyleBuilder> for String {
     }
 }
 
+impl AttrValue for StyleBuilder {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.into())
+    }
+}
+
 impl StyleBuilder {
     #[inline(always)]
     pub fn class(mut self, class: impl Into<String>) -> Self {
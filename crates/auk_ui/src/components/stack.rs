@@ -2,36 +2,30 @@ use auk::*;
 
 use crate::class;
 
-enum Direction {
+pub enum Direction {
     Vertical,
     Horizontal,
 }
 
+#[derive(Component)]
 pub struct Stack {
     direction: Direction,
-    classes: Vec<String>,
+    #[prop(skip, default = div())]
+    element: HtmlElement,
     children: Vec<Element>,
 }
 
 impl Stack {
     pub fn vertical() -> Self {
-        Self {
-            direction: Direction::Vertical,
-            classes: Vec::new(),
-            children: Vec::new(),
-        }
+        Self::new(Direction::Vertical)
     }
 
     pub fn horizontal() -> Self {
-        Self {
-            direction: Direction::Horizontal,
-            classes: Vec::new(),
-            children: Vec::new(),
-        }
+        Self::new(Direction::Horizontal)
     }
 
     pub fn class(mut self, class: impl Into<String>) -> Self {
-        self.classes.push(class.into());
+        self.element = self.element.add_class(class);
         self
     }
 
@@ -40,26 +34,13 @@ impl Stack {
     auk_plumage::gap!();
 }
 
-impl With for Stack {}
-
-impl WithChildren for Stack {
-    fn extend(&mut self, children: impl IntoIterator<Item = Element>) {
-        self.children.extend(children)
-    }
-}
-
 impl Render for Stack {
     fn render(self) -> impl Into<HtmlElement> {
-        div()
-            .class(
-                class()
-                    .flex()
-                    .with(|class| match self.direction {
-                        Direction::Vertical => class.flex_col(),
-                        Direction::Horizontal => class.flex_row(),
-                    })
-                    .extend(self.classes),
-            )
+        self.element
+            .add_class(class().flex().with(|class| match self.direction {
+                Direction::Vertical => class.flex_col(),
+                Direction::Horizontal => class.flex_row(),
+            }))
             .children(self.children)
     }
 }
@@ -1,4 +1,4 @@
-use auk::With;
+use auk::{AttrValue, With};
 
 pub fn class() -> StyleBuilder {
     StyleBuilder {
@@ -18,6 +18,12 @@ impl From<StyleBuilder> for String {
     }
 }
 
+impl AttrValue for StyleBuilder {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.into())
+    }
+}
+
 impl StyleBuilder {
     #[inline(always)]
     pub fn class(mut self, class: impl Into<String>) -> Self {